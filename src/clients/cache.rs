@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use moka::future::Cache;
+
+use super::tibia::SharedResponse;
+
+/// How long responses are cached, unless configured through `CACHE_TTL_SECONDS`
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+/// The number of body bytes kept, the least recently used responses are evicted first
+const MAX_CAPACITY_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Keeps the responses of tibia.com in memory for a short while, so that
+/// repeated requests for the same page don't reach tibia.com
+#[derive(Clone)]
+pub struct TibiaCache {
+    cache: Cache<String, SharedResponse>,
+}
+
+impl std::fmt::Debug for TibiaCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TibiaCache")
+            .field("entries", &self.cache.entry_count())
+            .field("time_to_live", &self.cache.policy().time_to_live())
+            .finish()
+    }
+}
+
+impl TibiaCache {
+    pub fn new(time_to_live: Duration) -> Self {
+        let cache = Cache::builder()
+            .max_capacity(MAX_CAPACITY_BYTES)
+            .weigher(|_, response: &SharedResponse| {
+                u32::try_from(response.body.len()).unwrap_or(u32::MAX)
+            })
+            .time_to_live(time_to_live)
+            .build();
+
+        Self { cache }
+    }
+
+    /// Uses the ttl from [`cache_ttl`]
+    pub fn from_env() -> Self {
+        Self::new(cache_ttl())
+    }
+
+    pub(super) async fn get(&self, key: &str) -> Option<SharedResponse> {
+        self.cache.get(key).await
+    }
+
+    pub(super) async fn insert(&self, key: String, response: SharedResponse) {
+        self.cache.insert(key, response).await
+    }
+}
+
+/// How long responses are cached, in seconds from `CACHE_TTL_SECONDS` or 60 seconds
+pub fn cache_ttl() -> Duration {
+    std::env::var("CACHE_TTL_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TTL)
+}
+
+impl Default for TibiaCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Bytes,
+        http::{HeaderMap, StatusCode},
+    };
+
+    use super::*;
+
+    fn response(body: &'static str) -> SharedResponse {
+        SharedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from(body),
+            fetched_at: std::time::Instant::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_cached_responses() {
+        let cache = TibiaCache::default();
+        cache.insert("key".to_string(), response("cached")).await;
+
+        let cached = cache.get("key").await.unwrap();
+
+        assert_eq!(Bytes::from("cached"), cached.body);
+        assert!(cache.get("other").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn expires_responses_after_ttl() {
+        let cache = TibiaCache::new(Duration::from_millis(50));
+        cache.insert("key".to_string(), response("cached")).await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(cache.get("key").await.is_none());
+    }
+}
@@ -1,2 +1,5 @@
+mod cache;
+mod single_flight;
 mod tibia;
+pub use cache::cache_ttl;
 pub use tibia::*;
@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+
+/// Deduplicates concurrent work with the same key, so that callers arriving
+/// while the work is in flight share its result instead of starting it again
+#[derive(Clone)]
+pub struct SingleFlight<T: Clone> {
+    in_flight: Arc<Mutex<HashMap<String, Shared<BoxFuture<'static, T>>>>>,
+}
+
+impl<T: Clone> std::fmt::Debug for SingleFlight<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let in_flight = self.in_flight.lock().map(|m| m.len()).unwrap_or_default();
+        f.debug_struct("SingleFlight")
+            .field("in_flight", &in_flight)
+            .finish()
+    }
+}
+
+impl<T: Clone> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self {
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SingleFlight<T> {
+    /// Runs `work`, unless work with the same key is already in flight,
+    /// in which case its result is awaited instead
+    pub async fn run<F>(&self, key: String, work: F) -> T
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let shared = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    let in_flight = self.in_flight.clone();
+                    async move {
+                        let result = work.await;
+                        // later callers should start over rather than get a stale result
+                        in_flight.lock().unwrap().remove(&key);
+                        result
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+
+        shared.await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn shares_concurrent_work_with_the_same_key() {
+        let single_flight = SingleFlight::default();
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let calls = (0..10).map(|_| {
+            let runs = runs.clone();
+            single_flight.run("key".to_string(), async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                runs.fetch_add(1, Ordering::SeqCst)
+            })
+        });
+        let results = futures::future::join_all(calls).await;
+
+        assert_eq!(1, runs.load(Ordering::SeqCst));
+        assert!(results.iter().all(|r| *r == 0));
+    }
+
+    #[tokio::test]
+    async fn runs_work_with_different_keys_separately() {
+        let single_flight = SingleFlight::default();
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let calls = (0..3).map(|n| {
+            let runs = runs.clone();
+            single_flight.run(n.to_string(), async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+            })
+        });
+        futures::future::join_all(calls).await;
+
+        assert_eq!(3, runs.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn runs_work_again_once_finished() {
+        let single_flight = SingleFlight::default();
+
+        let first = single_flight.run("key".to_string(), async { 1 }).await;
+        let second = single_flight.run("key".to_string(), async { 2 }).await;
+
+        assert_eq!((1, 2), (first, second));
+    }
+}
@@ -1,15 +1,62 @@
+use axum::{
+    body::Bytes,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+};
+use futures::FutureExt;
 use http_cache_reqwest::{CACacheManager, Cache, CacheMode, HttpCache, HttpCacheOptions};
-use reqwest_middleware::ClientWithMiddleware;
-use std::{collections::HashMap, time::Duration};
+use reqwest_middleware::{ClientWithMiddleware, RequestBuilder};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tracing::instrument;
 
-use crate::{models::ResidenceType, prelude::error_chain_fmt};
+use super::{cache::TibiaCache, single_flight::SingleFlight};
+use crate::{
+    models::{BazaarFilters, HighscoreCategory, ResidenceType, Vocation},
+    prelude::error_chain_fmt,
+    utils::data_age::DataAge,
+};
 
 const COMMUNITY_URL: &str = "https://www.tibia.com/community/";
+const LIBRARY_URL: &str = "https://www.tibia.com/library/";
+const NEWS_URL: &str = "https://www.tibia.com/news/";
+const CHARACTER_TRADE_URL: &str = "https://www.tibia.com/charactertrade/";
+/// The largest page read from tibia.com, unless configured through `MAX_UPSTREAM_BODY_BYTES`
+const DEFAULT_MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
 
 #[derive(Debug, Clone)]
 pub struct TibiaClient {
     client: ClientWithMiddleware,
+    in_flight: SingleFlight<Result<SharedResponse, Arc<TibiaError>>>,
+    cache: TibiaCache,
+    max_body_bytes: usize,
+}
+
+/// A fully read response, which unlike `reqwest::Response` can be shared
+/// between the callers of a single flight
+#[derive(Debug, Clone)]
+pub(super) struct SharedResponse {
+    pub(super) status: StatusCode,
+    pub(super) headers: HeaderMap,
+    pub(super) body: Bytes,
+    /// When the response was received from tibia.com, which is earlier than
+    /// its use for responses from the cache
+    pub(super) fetched_at: Instant,
+}
+
+impl From<SharedResponse> for reqwest::Response {
+    /// The response carries its [`DataAge`] as an extension, see [`DataAge::of`]
+    fn from(shared: SharedResponse) -> Self {
+        let mut response = axum::http::Response::new(shared.body);
+        *response.status_mut() = shared.status;
+        *response.headers_mut() = shared.headers;
+        response
+            .extensions_mut()
+            .insert(DataAge::fetched_at(shared.fetched_at));
+        response.into()
+    }
 }
 
 #[derive(thiserror::Error)]
@@ -18,8 +65,13 @@ pub enum TibiaError {
     Maintenance,
     #[error("The content on the page is not what was requested")]
     NotFound,
-    #[error("Getting rate limited")]
+    #[error("The request failed with status {0}")]
     UnsuccessfulRequest(reqwest::StatusCode),
+    /// tibia.com responded with 429, with the seconds to wait from its `Retry-After`, if any
+    #[error("Getting rate limited")]
+    RateLimited(Option<u64>),
+    #[error("The page is larger than the limit of {0} bytes")]
+    ResponseTooLarge(usize),
     #[error(transparent)]
     Reqwest(#[from] reqwest_middleware::Error),
 }
@@ -30,13 +82,34 @@ impl std::fmt::Debug for TibiaError {
     }
 }
 
+impl TibiaError {
+    /// Recreates an error shared between the callers of a single flight,
+    /// the underlying request error is only kept as a message
+    fn duplicate(&self) -> Self {
+        match self {
+            TibiaError::Maintenance => TibiaError::Maintenance,
+            TibiaError::NotFound => TibiaError::NotFound,
+            TibiaError::UnsuccessfulRequest(status) => TibiaError::UnsuccessfulRequest(*status),
+            TibiaError::RateLimited(retry_after) => TibiaError::RateLimited(*retry_after),
+            TibiaError::ResponseTooLarge(limit) => TibiaError::ResponseTooLarge(*limit),
+            TibiaError::Reqwest(e) => TibiaError::Reqwest(reqwest_middleware::Error::Middleware(
+                anyhow::anyhow!(e.to_string()),
+            )),
+        }
+    }
+}
+
 pub const MAINTENANCE_TITLE: &str =
     "Tibia - Free Multiplayer Online Role Playing Game - Maintenance";
 
+/// The pages of tibia.com, the handlers fetch them only through this trait
+/// so that they can be tested with a mocked client
 #[async_trait::async_trait]
 pub trait Client: Send + Sync + Clone + 'static {
     async fn fetch_towns_page(&self) -> Result<reqwest::Response, TibiaError>;
     async fn fetch_worlds_page(&self) -> Result<reqwest::Response, TibiaError>;
+    /// Fetches the worlds page past every cache, to tell whether tibia.com is up right now
+    async fn probe_worlds_page(&self) -> Result<reqwest::Response, TibiaError>;
     async fn fetch_world_details_page(
         &self,
         world_name: &str,
@@ -52,6 +125,29 @@ pub trait Client: Send + Sync + Clone + 'static {
         residence_type: &ResidenceType,
         town: &str,
     ) -> Result<reqwest::Response, TibiaError>;
+    async fn fetch_character_page(
+        &self,
+        character_name: &str,
+    ) -> Result<reqwest::Response, TibiaError>;
+    async fn fetch_guild_page(&self, guild_name: &str) -> Result<reqwest::Response, TibiaError>;
+    async fn fetch_creature_page(
+        &self,
+        creature_name: &str,
+    ) -> Result<reqwest::Response, TibiaError>;
+    async fn fetch_spells_page(&self) -> Result<reqwest::Response, TibiaError>;
+    async fn fetch_bazaar_page(
+        &self,
+        page: u32,
+        filters: &BazaarFilters,
+    ) -> Result<reqwest::Response, TibiaError>;
+    async fn fetch_news_page(&self) -> Result<reqwest::Response, TibiaError>;
+    async fn fetch_highscores_page(
+        &self,
+        world_name: &str,
+        category: &HighscoreCategory,
+        vocation: Option<Vocation>,
+        page: u32,
+    ) -> Result<reqwest::Response, TibiaError>;
 }
 
 impl TibiaClient {
@@ -77,7 +173,67 @@ impl TibiaClient {
             }))
             .build();
 
-        Self { client }
+        Self {
+            client,
+            in_flight: SingleFlight::default(),
+            cache: TibiaCache::from_env(),
+            max_body_bytes: max_body_bytes(),
+        }
+    }
+
+    /// Sends the request, unless its response is cached, sharing the response
+    /// with identical requests that are already in flight.
+    ///
+    /// Fails with [`TibiaError::RateLimited`] for 429 and with
+    /// [`TibiaError::UnsuccessfulRequest`] for other error statuses.
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, TibiaError> {
+        let request = request.build().map_err(reqwest_middleware::Error::from)?;
+        let key = flight_key(request.url());
+        if let Some(cached) = self.cache.get(&key).await {
+            return Ok(cached.into());
+        }
+        let client = self.client.clone();
+        let cache = self.cache.clone();
+        let max_body_bytes = self.max_body_bytes;
+
+        let result = self
+            .in_flight
+            .run(
+                key.clone(),
+                async move {
+                    let response = client.execute(request).await?;
+                    let status = response.status();
+                    let mut headers = response.headers().clone();
+                    let body = read_body(response, max_body_bytes).await?;
+                    let body = to_utf8(&mut headers, body);
+
+                    let shared = SharedResponse {
+                        status,
+                        headers,
+                        body,
+                        fetched_at: Instant::now(),
+                    };
+                    // errors are left uncached, to be retried right away
+                    if status.is_success() {
+                        cache.insert(key, shared.clone()).await;
+                    }
+
+                    Ok(shared)
+                }
+                .map(|result: Result<SharedResponse, TibiaError>| result.map_err(Arc::new)),
+            )
+            .await;
+
+        match result {
+            Ok(shared) if shared.status == StatusCode::TOO_MANY_REQUESTS => {
+                Err(TibiaError::RateLimited(retry_after(&shared.headers)))
+            }
+            Ok(shared) if shared.status.is_client_error() || shared.status.is_server_error() => {
+                Err(TibiaError::UnsuccessfulRequest(shared.status))
+            }
+            Ok(shared) => Ok(shared.into()),
+            Err(e) => Err(Arc::try_unwrap(e).unwrap_or_else(|e| e.duplicate())),
+        }
     }
 }
 
@@ -87,16 +243,127 @@ impl Default for TibiaClient {
     }
 }
 
+/// The largest page read from tibia.com, in bytes from `MAX_UPSTREAM_BODY_BYTES` or 5 MB
+pub fn max_body_bytes() -> usize {
+    std::env::var("MAX_UPSTREAM_BODY_BYTES")
+        .ok()
+        .and_then(|bytes| bytes.parse().ok())
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES)
+}
+
+/// Reads the (decompressed) body chunk by chunk, giving up as soon as it grows
+/// beyond `max_bytes` instead of buffering all of it first
+async fn read_body(mut response: reqwest::Response, max_bytes: usize) -> Result<Bytes, TibiaError> {
+    if let Some(length) = response.content_length() {
+        if length > max_bytes as u64 {
+            return Err(TibiaError::ResponseTooLarge(max_bytes));
+        }
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(reqwest_middleware::Error::from)?
+    {
+        if body.len() + chunk.len() > max_bytes {
+            return Err(TibiaError::ResponseTooLarge(max_bytes));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.into())
+}
+
+/// The charsets decoded as windows-1252, tibia.com serves some of its pages with them
+const LATIN_1_CHARSETS: &[&str] = &["iso-8859-1", "latin1", "latin-1", "windows-1252"];
+
+/// Transcodes latin-1 bodies to utf-8 and labels them as such, so that
+/// names like "Hälge" decode correctly even when tibia.com mislabels the page.
+///
+/// Latin-1 is decoded as its superset windows-1252, like browsers do, which
+/// only differ in the bytes 0x80 to 0x9f, such as "€" and "…".
+/// Bodies without a latin-1 charset are kept as they are when they are valid
+/// utf-8, and are otherwise assumed to be latin-1.
+fn to_utf8(headers: &mut HeaderMap, body: Bytes) -> Bytes {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("text/html");
+    let mut parts = content_type.split(';').map(str::trim);
+    let mime_type = parts.next().unwrap_or("text/html").to_string();
+    let charset = parts
+        .filter_map(|part| part.split_once('='))
+        .find(|(name, _)| name.eq_ignore_ascii_case("charset"))
+        .map(|(_, charset)| charset.trim_matches('"').to_lowercase());
+
+    let is_latin_1 = charset.is_some_and(|charset| LATIN_1_CHARSETS.contains(&charset.as_str()));
+    if !is_latin_1 && std::str::from_utf8(&body).is_ok() {
+        return body;
+    }
+
+    let (text, _) = encoding_rs::WINDOWS_1252.decode_without_bom_handling(&body);
+    if let Ok(value) = HeaderValue::from_str(&format!("{mime_type}; charset=utf-8")) {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    headers.remove(header::CONTENT_LENGTH);
+
+    text.into_owned().into()
+}
+
+/// The seconds to wait from a `Retry-After` header, dates are ignored
+fn retry_after(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Identifies a request by its url, regardless of the order of the query parameters
+fn flight_key(url: &reqwest::Url) -> String {
+    let mut pairs = url.query_pairs().collect::<Vec<_>>();
+    pairs.sort();
+    let mut key = url.clone();
+    key.query_pairs_mut().clear().extend_pairs(pairs);
+
+    key.to_string()
+}
+
 #[async_trait::async_trait]
 impl Client for TibiaClient {
     #[instrument(skip(self))]
     async fn fetch_worlds_page(&self) -> Result<reqwest::Response, TibiaError> {
         let mut params = HashMap::new();
         params.insert("subtopic", "worlds");
-        let response = self.client.get(COMMUNITY_URL).query(&params).send().await?;
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn probe_worlds_page(&self) -> Result<reqwest::Response, TibiaError> {
+        let mut params = HashMap::new();
+        params.insert("subtopic", "worlds");
+        let request = self
+            .client
+            .get(COMMUNITY_URL)
+            .query(&params)
+            .header(header::CACHE_CONTROL, "no-cache")
+            .build()
+            .map_err(reqwest_middleware::Error::from)?;
 
-        if response.status().as_u16() > 399 {
-            return Err(TibiaError::UnsuccessfulRequest(response.status()))?;
+        let response = self.client.execute(request).await?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(TibiaError::RateLimited(retry_after(response.headers())));
+        }
+        if status.is_client_error() || status.is_server_error() {
+            return Err(TibiaError::UnsuccessfulRequest(status));
         }
 
         Ok(response)
@@ -107,7 +374,9 @@ impl Client for TibiaClient {
         let mut params = HashMap::new();
         params.insert("subtopic", "houses");
 
-        let response = self.client.get(COMMUNITY_URL).query(&params).send().await?;
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
         Ok(response)
     }
 
@@ -119,7 +388,9 @@ impl Client for TibiaClient {
         let mut params = HashMap::new();
         params.insert("subtopic", "worlds");
         params.insert("world", world_name);
-        let response = self.client.get(COMMUNITY_URL).query(&params).send().await?;
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
 
         Ok(response)
     }
@@ -129,7 +400,9 @@ impl Client for TibiaClient {
         let mut params = HashMap::new();
         params.insert("subtopic", "guilds");
         params.insert("world", world_name);
-        let response = self.client.get(COMMUNITY_URL).query(&params).send().await?;
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
 
         Ok(response)
     }
@@ -142,7 +415,9 @@ impl Client for TibiaClient {
         let mut params = HashMap::new();
         params.insert("subtopic", "killstatistics");
         params.insert("world", world_name);
-        let response = self.client.get(COMMUNITY_URL).query(&params).send().await?;
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
 
         Ok(response)
     }
@@ -163,8 +438,304 @@ impl Client for TibiaClient {
             ResidenceType::Guildhall => "guildhalls",
         };
         params.insert("type", residence_string);
-        let response = self.client.get(COMMUNITY_URL).query(&params).send().await?;
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
 
         Ok(response)
     }
+
+    #[instrument(skip(self))]
+    async fn fetch_character_page(
+        &self,
+        character_name: &str,
+    ) -> Result<reqwest::Response, TibiaError> {
+        let mut params = HashMap::new();
+        params.insert("subtopic", "characters");
+        params.insert("name", character_name);
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_guild_page(&self, guild_name: &str) -> Result<reqwest::Response, TibiaError> {
+        let mut params = HashMap::new();
+        params.insert("subtopic", "guilds");
+        params.insert("page", "view");
+        params.insert("GuildName", guild_name);
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_creature_page(
+        &self,
+        creature_name: &str,
+    ) -> Result<reqwest::Response, TibiaError> {
+        // the library identifies creatures by their lowercased name without spaces
+        let race = creature_name.to_lowercase().replace(' ', "");
+        let mut params = HashMap::new();
+        params.insert("subtopic", "creatures");
+        params.insert("race", race.as_str());
+        let response = self
+            .send(self.client.get(LIBRARY_URL).query(&params))
+            .await?;
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_spells_page(&self) -> Result<reqwest::Response, TibiaError> {
+        let mut params = HashMap::new();
+        params.insert("subtopic", "spells");
+        let response = self
+            .send(self.client.get(LIBRARY_URL).query(&params))
+            .await?;
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_bazaar_page(
+        &self,
+        page: u32,
+        filters: &BazaarFilters,
+    ) -> Result<reqwest::Response, TibiaError> {
+        let page = page.to_string();
+        let min_level = filters.min_level.map(|l| l.to_string());
+        let mut params = HashMap::new();
+        params.insert("subtopic", "currentcharactertrades");
+        params.insert("currentpage", page.as_str());
+        if let Some(world) = &filters.world {
+            params.insert("filter_world", world.as_str());
+        }
+        if let Some(vocation) = filters.vocation {
+            // tibia only filters by the vocation before promotion
+            let profession = match vocation.base() {
+                Vocation::Druid => "2",
+                Vocation::Knight => "3",
+                Vocation::Paladin => "4",
+                // only sorcerers are left after taking the base vocation
+                _ => "5",
+            };
+            params.insert("filter_profession", profession);
+        }
+        if let Some(min_level) = &min_level {
+            params.insert("filter_levelrangefrom", min_level.as_str());
+        }
+        let response = self
+            .send(self.client.get(CHARACTER_TRADE_URL).query(&params))
+            .await?;
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_news_page(&self) -> Result<reqwest::Response, TibiaError> {
+        let mut params = HashMap::new();
+        params.insert("subtopic", "latestnews");
+        let response = self.send(self.client.get(NEWS_URL).query(&params)).await?;
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self))]
+    async fn fetch_highscores_page(
+        &self,
+        world_name: &str,
+        category: &HighscoreCategory,
+        vocation: Option<Vocation>,
+        page: u32,
+    ) -> Result<reqwest::Response, TibiaError> {
+        let category = category.tibia_id().to_string();
+        let page = page.to_string();
+        // tibia only filters by the vocation before promotion
+        let profession = match vocation.map(Vocation::base) {
+            None => "0",
+            Some(Vocation::Knight) => "2",
+            Some(Vocation::Paladin) => "3",
+            Some(Vocation::Sorcerer) => "4",
+            // only druids are left after taking the base vocation
+            Some(_) => "5",
+        };
+        let mut params = HashMap::new();
+        params.insert("subtopic", "highscores");
+        params.insert("world", world_name);
+        params.insert("category", category.as_str());
+        params.insert("profession", profession);
+        params.insert("currentpage", page.as_str());
+        let response = self
+            .send(self.client.get(COMMUNITY_URL).query(&params))
+            .await?;
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_after_reads_seconds() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(None, retry_after(&headers));
+
+        headers.insert(header::RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(Some(120), retry_after(&headers));
+
+        headers.insert(
+            header::RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(None, retry_after(&headers));
+    }
+
+    #[test]
+    fn flight_key_ignores_query_parameter_order() {
+        let a =
+            reqwest::Url::parse("https://www.tibia.com/community/?subtopic=worlds&world=Antica")
+                .unwrap();
+        let b =
+            reqwest::Url::parse("https://www.tibia.com/community/?world=Antica&subtopic=worlds")
+                .unwrap();
+
+        assert_eq!(flight_key(&a), flight_key(&b));
+    }
+
+    fn headers(content_type: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+        headers
+    }
+
+    #[test]
+    fn transcodes_latin_1_bodies() {
+        let mut headers = headers("text/html; charset=ISO-8859-1");
+        let body = Bytes::from_static(b"<td>H\xe4lge</td>");
+
+        let body = to_utf8(&mut headers, body);
+
+        assert_eq!("<td>Hälge</td>", std::str::from_utf8(&body).unwrap());
+        assert_eq!("text/html; charset=utf-8", headers[header::CONTENT_TYPE]);
+    }
+
+    #[test]
+    fn transcodes_windows_1252_bodies() {
+        let mut headers = headers("text/html; charset=windows-1252");
+        let body = Bytes::from_static(b"<td>100 \x80 \x96 H\xe4lge\x85</td>");
+
+        let body = to_utf8(&mut headers, body);
+
+        assert_eq!(
+            "<td>100 € – Hälge…</td>",
+            std::str::from_utf8(&body).unwrap()
+        );
+    }
+
+    #[test]
+    fn falls_back_to_latin_1_for_mislabelled_bodies() {
+        let mut headers = headers("text/html; charset=utf-8");
+        let body = Bytes::from_static(b"<td>H\xe4lge</td>");
+
+        let body = to_utf8(&mut headers, body);
+
+        assert_eq!("<td>Hälge</td>", std::str::from_utf8(&body).unwrap());
+    }
+
+    #[test]
+    fn keeps_utf_8_bodies() {
+        let mut headers = headers("text/html; charset=utf-8");
+        let body = Bytes::from("<td>Hälge</td>");
+
+        let body = to_utf8(&mut headers, body);
+
+        assert_eq!("<td>Hälge</td>", std::str::from_utf8(&body).unwrap());
+    }
+
+    fn response(body: &'static str) -> reqwest::Response {
+        axum::http::Response::new(body).into()
+    }
+
+    #[tokio::test]
+    async fn reads_bodies_up_to_the_limit() {
+        let body = read_body(response("0123456789"), 10).await.unwrap();
+
+        assert_eq!(Bytes::from("0123456789"), body);
+    }
+
+    #[tokio::test]
+    async fn rejects_bodies_over_the_limit() {
+        let result = read_body(response("0123456789"), 9).await;
+
+        assert!(matches!(result, Err(TibiaError::ResponseTooLarge(9))));
+    }
+
+    #[tokio::test]
+    async fn serves_cached_responses_without_sending_them() {
+        let client = TibiaClient::new();
+        // nothing listens on the discard port, so only the cache can answer
+        let url = "http://127.0.0.1:9/community/?subtopic=worlds";
+        let cached = SharedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from("cached"),
+            fetched_at: Instant::now() - Duration::from_secs(60),
+        };
+        let key = flight_key(&reqwest::Url::parse(url).unwrap());
+        client.cache.insert(key, cached).await;
+
+        let response = client.send(client.client.get(url)).await.unwrap();
+
+        assert!(DataAge::of(&response).seconds() >= 60);
+        assert_eq!("cached", response.text().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn cached_responses_are_served_without_waiting_for_tibia() {
+        let delay = Duration::from_millis(500);
+        let fetches = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let upstream = axum::Router::new().route(
+            "/community/",
+            axum::routing::get({
+                let fetches = fetches.clone();
+                move || async move {
+                    fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    tokio::time::sleep(delay).await;
+                    "fetched"
+                }
+            }),
+        );
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/community/?subtopic=worlds",
+            listener.local_addr().unwrap()
+        );
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(upstream.into_make_service()),
+        );
+        let client = TibiaClient::new();
+
+        let started = Instant::now();
+        let uncached = client.send(client.client.get(&url)).await.unwrap();
+        let uncached_latency = started.elapsed();
+
+        let started = Instant::now();
+        let cached = client.send(client.client.get(&url)).await.unwrap();
+        let cached_latency = started.elapsed();
+
+        assert_eq!("fetched", uncached.text().await.unwrap());
+        assert_eq!("fetched", cached.text().await.unwrap());
+        assert!(uncached_latency >= delay);
+        assert!(cached_latency < delay / 10);
+        assert_eq!(1, fetches.load(std::sync::atomic::Ordering::SeqCst));
+    }
 }
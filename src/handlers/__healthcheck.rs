@@ -1,6 +1,89 @@
-use axum::{response::IntoResponse, Json};
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::Deserialize;
 use serde_json::json;
 
-pub async fn get() -> impl IntoResponse {
-    Json(json!({ "status": "ok" }))
+use crate::{
+    clients::{Client, MAINTENANCE_TITLE},
+    selectors,
+    utils::extract::Query,
+    AppState,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// Also check that tibia.com is reachable (defaults to `false`)
+    deep: Option<bool>,
+}
+
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Query(query_params): Query<QueryParams>,
+) -> impl IntoResponse {
+    let cache_warmed_up = state.cache_warmed_up_at.lock().unwrap().is_some();
+    // how stale the data is, when tibia.com can't be reached for a while
+    let last_successful_fetch = *state.last_successful_fetch.lock().unwrap();
+
+    if !query_params.deep.unwrap_or(false) {
+        return (
+            StatusCode::OK,
+            Json(json!({
+                "status": "ok",
+                "cacheWarmedUp": cache_warmed_up,
+                "lastSuccessfulFetch": last_successful_fetch
+            })),
+        );
+    }
+
+    if tibia_reachable(&state.client).await {
+        (
+            StatusCode::OK,
+            Json(json!({
+                "status": "ok",
+                "cacheWarmedUp": cache_warmed_up,
+                "lastSuccessfulFetch": last_successful_fetch,
+                "tibiaReachable": true
+            })),
+        )
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "status": "degraded",
+                "cacheWarmedUp": cache_warmed_up,
+                "lastSuccessfulFetch": last_successful_fetch,
+                "tibiaReachable": false
+            })),
+        )
+    }
+}
+
+/// Whether tibia.com serves the worlds page, rather than an error or the maintenance page.
+///
+/// The page is probed past the caches, which would otherwise keep reporting
+/// tibia.com as reachable for as long as they hold the page.
+async fn tibia_reachable<S: Client>(client: &S) -> bool {
+    let text = match client.probe_worlds_page().await {
+        Ok(response) => response.text().await,
+        Err(e) => {
+            tracing::warn!("Failed to reach tibia.com: {:?}", e);
+            return false;
+        }
+    };
+    let Ok(text) = text else {
+        tracing::warn!("Failed to read the worlds page of tibia.com");
+        return false;
+    };
+
+    let document = scraper::Html::parse_document(&text);
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+    if MAINTENANCE_TITLE == title {
+        tracing::warn!("tibia.com is under maintenance");
+        return false;
+    }
+
+    true
 }
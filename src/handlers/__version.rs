@@ -0,0 +1,31 @@
+use axum::{response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+/// Version
+///
+/// The version of the running build, left out of the documented routes like `/__healthcheck`.
+#[utoipa::path(
+    get,
+    operation_id = "get_version",
+    path = "/__version",
+    responses(
+        (status = 200, description = "Success", example = json!({
+            "version": "0.1.0",
+            "gitSha": "abc1234",
+            "builtAt": "2024-01-15T12:00:00Z"
+        }))
+    )
+)]
+pub async fn get() -> impl IntoResponse {
+    let built_at = env!("BUILT_AT")
+        .parse()
+        .ok()
+        .and_then(|seconds| DateTime::<Utc>::from_timestamp(seconds, 0));
+
+    Json(json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "gitSha": env!("GIT_SHA"),
+        "builtAt": built_at,
+    }))
+}
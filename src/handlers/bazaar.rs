@@ -0,0 +1,238 @@
+use anyhow::{Context, Result};
+use axum::{extract::State, Json};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Europe::Stockholm;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    models::{BazaarFilters, CharacterAuction, Vocation},
+    prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+static AUCTION_TIME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(\w{3} \d{2} \d{4}, \d{2}:\d{2}) CES?T").expect("Invalid auction time regex")
+});
+static AUCTION_ID_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"auctionid=(\d+)").expect("Invalid auction id regex"));
+static AUCTION_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Level: (\d+) \| Vocation: (.*) \| (Male|Female) \| World: (.*)$")
+        .expect("Invalid auction header regex")
+});
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// The page of auctions to return, starting from 1
+    #[param(example = 1)]
+    page: Option<u32>,
+    /// Only include characters on this world
+    #[param(example = "Antica")]
+    world: Option<String>,
+    /// Only include characters with this vocation (promoted or not)
+    vocation: Option<Vocation>,
+    /// Only include characters of at least this level
+    #[param(example = 100)]
+    min_level: Option<u16>,
+}
+
+/// Character Bazaar
+///
+/// The characters currently up for auction.
+#[utoipa::path(
+    get,
+    operation_id = "get_bazaar",
+    path = "/api/v1/bazaar",
+    params(QueryParams),
+    responses(
+        (status = 200, description = "Success", body = [CharacterAuction], example = json!([
+            {
+                "id": 1234567,
+                "name": "Knight Of Antica",
+                "level": 312,
+                "vocation": "eliteKnight",
+                "sex": "male",
+                "world": "Antica",
+                "bid": 2500,
+                "hasBid": true,
+                "startedAt": "2023-09-16T08:00:00Z",
+                "endsAt": "2023-09-20T08:00:00Z"
+            }
+        ])),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "page must be at least 1"
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Bazaar"
+)]
+#[instrument(name = "Get Bazaar", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<Vec<CharacterAuction>>), ServerError> {
+    let client = &state.client;
+
+    let page = query_params.page.unwrap_or(1);
+    if page < 1 {
+        return Err(ServerError::InvalidParameter(
+            "page must be at least 1".to_string(),
+        ));
+    }
+    let filters = BazaarFilters {
+        world: query_params.world,
+        vocation: query_params.vocation,
+        min_level: query_params.min_level,
+    };
+
+    let response = client
+        .fetch_bazaar_page(page, &filters)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch bazaar page: {:?}", e);
+            e
+        })?;
+    let data_age = DataAge::of(&response);
+    let auctions = parse_bazaar_page(response).await.map_err(|e| {
+        tracing::error!("Failed to parse bazaar page: {:?}", e);
+        e
+    })?;
+
+    Ok((data_age, Json(auctions)))
+}
+
+#[instrument(skip(response))]
+async fn parse_bazaar_page(response: Response) -> Result<Vec<CharacterAuction>, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    let mut auctions = vec![];
+    for auction in main_content.select(&selectors::AUCTION) {
+        let name_link = auction
+            .select(&selectors::AUCTION_CHARACTER_NAME)
+            .next()
+            .context("Auction character name not found")?;
+        let name = name_link.text().collect::<String>().sanitize();
+        let id = name_link
+            .value()
+            .attr("href")
+            .and_then(|href| AUCTION_ID_RE.captures(href))
+            .and_then(|c| c.get(1))
+            .and_then(|id| id.as_str().parse().ok())
+            .context(format!("Failed to parse auction id for {}", name))?;
+
+        let header = auction
+            .select(&selectors::AUCTION_HEADER)
+            .next()
+            .context("Auction header not found")?
+            .text()
+            .collect::<String>()
+            .sanitize();
+        let header = header.trim_start_matches(&name).trim();
+        let captures = AUCTION_HEADER_RE
+            .captures(header)
+            .context(format!("Unexpected auction header {}", header))?;
+        let level = captures
+            .get(1)
+            .and_then(|l| l.as_str().parse().ok())
+            .context(format!("Failed to parse auction level in {}", header))?;
+        let vocation = match captures.get(2).map(|v| v.as_str()) {
+            Some("None") | None => None,
+            Some(v) => Some(v.parse()?),
+        };
+        let sex = captures
+            .get(3)
+            .context("Auction sex not found")?
+            .as_str()
+            .to_lowercase()
+            .parse()?;
+        let world = captures
+            .get(4)
+            .context("Auction world not found")?
+            .as_str()
+            .to_string();
+
+        let data = auction
+            .select(&selectors::AUCTION_DATA_LABEL)
+            .map(|l| l.text().collect::<String>().sanitize())
+            .zip(
+                auction
+                    .select(&selectors::AUCTION_DATA_VALUE)
+                    .map(|v| v.text().collect::<String>().sanitize()),
+            )
+            .collect::<Vec<_>>();
+        let (bid_label, bid) = data
+            .iter()
+            .find(|(label, _)| label.ends_with("Bid:"))
+            .context(format!("Bid not found for auction {}", id))?;
+        let (_, started_at) = data
+            .iter()
+            .find(|(label, _)| label == "Auction Start:")
+            .context(format!("Auction start not found for auction {}", id))?;
+        let (_, ends_at) = data
+            .iter()
+            .find(|(label, _)| label == "Auction End:")
+            .context(format!("Auction end not found for auction {}", id))?;
+        // bids are in Tibia Coins, shown as a number next to a coin image
+        let bid = bid
+            .replace(',', "")
+            .parse()
+            .context(format!("Failed to parse bid {} of auction {}", bid, id))?;
+
+        auctions.push(CharacterAuction {
+            id,
+            name,
+            level,
+            vocation,
+            sex,
+            world,
+            bid,
+            has_bid: bid_label == "Current Bid:",
+            started_at: parse_auction_time(started_at)?,
+            ends_at: parse_auction_time(ends_at)?,
+        });
+    }
+
+    Ok(auctions)
+}
+
+/// Parses a time such as `Oct 16 2023, 18:00 CEST`, in the time zone of the servers
+fn parse_auction_time(text: &str) -> Result<DateTime<Utc>> {
+    let time = AUCTION_TIME_RE
+        .captures(text)
+        .and_then(|c| c.get(1))
+        .context(format!("Auction time not found in {}", text))?
+        .as_str();
+    let naive_dt = NaiveDateTime::parse_from_str(time, "%b %d %Y, %H:%M")
+        .context(format!("Failed to parse auction time {}", time))?;
+
+    Stockholm
+        .from_local_datetime(&naive_dt)
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+        .context(format!("Ambiguous auction time {}", time))
+}
@@ -0,0 +1,113 @@
+use axum::{extract::State, Json};
+use futures::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use super::characters_character_name::get_character;
+use crate::{
+    models::{CharacterLookupError, CharacterResult},
+    prelude::*,
+    utils::data_age::DataAge,
+    AppState,
+};
+
+/// The maximum number of characters that can be requested at once
+pub const MAX_CHARACTER_NAMES: usize = 10;
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CharactersBody {
+    /// Names of the characters to look up (at most 10)
+    #[schema(example = json!(["Urinchoklad", "Hälge"]))]
+    names: Vec<String>,
+}
+
+/// Characters
+///
+/// Looks up multiple characters at once. Characters that don't exist are
+/// reported with an error instead of failing the whole request.
+#[utoipa::path(
+    post,
+    operation_id = "post_characters",
+    path = "/api/v1/characters",
+    request_body = CharactersBody,
+    responses(
+        (status = 200, description = "Success", body = [CharacterResult], example = json!([
+            {
+                "name": "Urinchoklad",
+                "data": {
+                    "name": "Urinchoklad",
+                    "formerNames": [],
+                    "title": "Aspiring Huntsman",
+                    "titlesUnlocked": 7,
+                    "sex": "male",
+                    "vocation": "eliteKnight",
+                    "level": 52,
+                    "achievementPoints": 23,
+                    "world": "Antica",
+                    "residence": "Thais",
+                    "houses": [],
+                    "lastLogin": "2023-07-07T17:49:44Z",
+                    "accountStatus": "premium"
+                }
+            },
+            {
+                "name": "Hälge",
+                "error": "not_found"
+            }
+        ])),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "At most 10 names can be requested at once"
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Characters"
+)]
+#[instrument(name = "Post Characters", skip(state))]
+pub async fn post<S: Client>(
+    State(state): State<AppState<S>>,
+    Json(body): Json<CharactersBody>,
+) -> Result<(DataAge, Json<Vec<CharacterResult>>), ServerError> {
+    let client = &state.client;
+
+    if body.names.len() > MAX_CHARACTER_NAMES {
+        return Err(ServerError::InvalidParameter(format!(
+            "At most {MAX_CHARACTER_NAMES} names can be requested at once"
+        )));
+    }
+
+    // fetch up to 5 characters in parallel (without preserving the order of the results)
+    let stream = futures::stream::iter(body.names.into_iter().enumerate())
+        .map(|(index, name)| async move {
+            let result = get_character(client, &name).await;
+            (index, name, result)
+        })
+        .buffer_unordered(5);
+
+    let mut results = stream.collect::<Vec<_>>().await;
+    // respond in the same order as the names were requested
+    results.sort_by_key(|(index, _, _)| *index);
+
+    let mut data_age = DataAge::now();
+    let characters = results
+        .into_iter()
+        .map(|(_, name, result)| match result {
+            Ok((character_data_age, data)) => {
+                data_age = data_age.oldest(character_data_age);
+                Ok(CharacterResult::Found {
+                    name,
+                    data: Box::new(data),
+                })
+            }
+            Err(ServerError::Client(TibiaError::NotFound)) => Ok(CharacterResult::Error {
+                name,
+                error: CharacterLookupError::NotFound,
+            }),
+            Err(e) => Err(e),
+        })
+        .collect::<Result<Vec<_>, ServerError>>()?;
+
+    Ok((data_age, Json(characters)))
+}
@@ -0,0 +1,412 @@
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{prelude::*, TimeZone, Utc};
+use chrono_tz::Europe::Stockholm;
+use regex::Regex;
+use reqwest::{Response, Url};
+use scraper::ElementRef;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    models::{AccountStatus, CharacterGuild, CharacterHouse, CharacterInfo, CharacterOutfit, Sex},
+    prelude::*,
+    selectors,
+    utils::{data_age::DataAge, parsing::parse_names},
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+pub struct PathParams {
+    /// Name of character
+    #[param(example = "Urinchoklad")]
+    pub character_name: String,
+}
+
+/// Character
+///
+#[utoipa::path(
+    get,
+    operation_id = "get_character",
+    path = "/api/v1/characters/{character_name}",
+    params(PathParams),
+    responses(
+        (status = 200, description = "Success", body = CharacterInfo, example = json!({
+            "name": "Urinchoklad",
+            "formerNames": ["Urinchoklad Jr"],
+            "title": "Aspiring Huntsman",
+            "titlesUnlocked": 7,
+            "loyaltyTitle": "Guardian of Tibia",
+            "sex": "male",
+            "vocation": "eliteKnight",
+            "level": 52,
+            "achievementPoints": 23,
+            "world": "Antica",
+            "worldUrl": "/api/v1/worlds/Antica",
+            "formerWorld": "Secura",
+            "formerWorldUrl": "/api/v1/worlds/Secura",
+            "residence": "Thais",
+            "dailyRewardStreak": 42,
+            "charmExpansion": true,
+            "huntingTaskPoints": 5000,
+            "outfit": {
+                "lookType": 128,
+                "head": 78,
+                "body": 69,
+                "legs": 58,
+                "feet": 76,
+                "addons": 3
+            },
+            "outfitUrl": "https://outfit-images.ots.me/animatedOutfits1099/animoutfit.php?id=128&addons=3&head=78&body=69&legs=58&feet=76&mount=0",
+            "houses": [
+                {
+                    "id": 50101,
+                    "name": "Castle Shop 1",
+                    "town": "Edron",
+                    "paidUntil": "2023-09-08"
+                }
+            ],
+            "guild": { "name": "Red Rose", "rank": "Leader" },
+            "lastLogin": "2023-07-07T17:49:44Z",
+            "accountStatus": "premium"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Characters"
+)]
+#[instrument(name = "Get Character", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+) -> Result<(DataAge, Json<CharacterInfo>), ServerError> {
+    let client = &state.client;
+    let (data_age, character) = get_character(client, &path_params.character_name).await?;
+
+    Ok((data_age, Json(character)))
+}
+
+#[instrument(skip(client))]
+pub async fn get_character<S: Client>(
+    client: &S,
+    character_name: &str,
+) -> Result<(DataAge, CharacterInfo), ServerError> {
+    let response = client
+        .fetch_character_page(character_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch character page: {:?}", e);
+            e
+        })?;
+    let data_age = DataAge::of(&response);
+    let character = parse_character_page(response, character_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to parse character page: {:?}", e);
+            e
+        })?;
+
+    Ok((data_age, character))
+}
+
+#[instrument(skip(response))]
+pub async fn parse_character_page(
+    response: Response,
+    character_name: &str,
+) -> Result<CharacterInfo, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    let Some(information_table) = find_table(&main_content, "Character Information") else {
+        tracing::info!("Character '{}' not found", character_name);
+        return Err(TibiaError::NotFound)?;
+    };
+
+    let title_re = Regex::new(r"^(.*) \((\d+) titles? unlocked\)$").expect("Invalid title regex");
+    let house_re =
+        Regex::new(r"\((.*)\) is paid until (.*)$").expect("Invalid house paid until regex");
+    let house_id_re = Regex::new(r"houseid=(\d+)").expect("Invalid house id regex");
+    let last_login_re = Regex::new(r"(.*) CES?T").expect("Invalid last login regex");
+
+    let mut character = CharacterInfo {
+        name: character_name.to_string(),
+        former_names: vec![],
+        title: None,
+        titles_unlocked: 0,
+        loyalty_title: None,
+        sex: Sex::Male,
+        vocation: None,
+        level: 0,
+        achievement_points: 0,
+        world: String::new(),
+        world_url: String::new(),
+        former_world: None,
+        former_world_url: None,
+        residence: String::new(),
+        daily_reward_streak: None,
+        charm_expansion: None,
+        hunting_task_points: None,
+        outfit: None,
+        outfit_url: None,
+        houses: vec![],
+        guild: None,
+        last_login: None,
+        comment: None,
+        account_status: AccountStatus::Free,
+    };
+
+    for row in information_table.select(&selectors::TABLE_CONTENT_ROW) {
+        let mut cells = row.select(&selectors::CELL);
+        let (Some(header), Some(value)) = (cells.next(), cells.next()) else {
+            continue;
+        };
+        let text = value.text().collect::<String>().sanitize();
+
+        match header.text().collect::<String>().sanitize().as_str() {
+            "Name:" => {
+                character.name = value
+                    .text()
+                    .next()
+                    .context("Character name not found")?
+                    .to_string()
+                    .sanitize();
+            }
+            "Former Names:" => {
+                // the names may be split across several text nodes
+                character.former_names = parse_names(&text);
+            }
+            "Title:" => {
+                let captures = title_re
+                    .captures(&text)
+                    .context(format!("Failed to parse title {}", text))?;
+                character.title = match &captures[1] {
+                    "None" => None,
+                    t => Some(t.to_string()),
+                };
+                character.titles_unlocked = captures[2]
+                    .parse()
+                    .context(format!("Failed to parse titles unlocked {}", text))?;
+            }
+            "Loyalty Title:" => {
+                character.loyalty_title = Some(text);
+            }
+            "Sex:" => {
+                character.sex = text.parse()?;
+            }
+            "Vocation:" => {
+                character.vocation = match text.as_str() {
+                    "None" => None,
+                    v => Some(v.parse()?),
+                };
+            }
+            "Level:" => {
+                character.level = text
+                    .parse()
+                    .context(format!("Failed to parse level {}", text))?;
+            }
+            "Achievement Points:" => {
+                character.achievement_points = text
+                    .replace(',', "")
+                    .parse()
+                    .context(format!("Failed to parse achievement points {}", text))?;
+            }
+            "World:" => {
+                character.world_url = world_url(&text);
+                character.world = text;
+            }
+            "Former World:" => {
+                character.former_world_url = Some(world_url(&text));
+                character.former_world = Some(text);
+            }
+            "Residence:" => {
+                character.residence = text;
+            }
+            "Daily Reward Streak:" => {
+                character.daily_reward_streak = Some(
+                    text.replace(',', "")
+                        .parse()
+                        .context(format!("Failed to parse daily reward streak {}", text))?,
+                );
+            }
+            "Charm Expansion:" => {
+                character.charm_expansion = match text.as_str() {
+                    "yes" => Some(true),
+                    "no" => Some(false),
+                    _ => return Err(anyhow::anyhow!("Unexpected charm expansion: '{}'", text))?,
+                };
+            }
+            "Hunting Task Points:" => {
+                character.hunting_task_points = Some(
+                    text.replace(',', "")
+                        .parse()
+                        .context(format!("Failed to parse hunting task points {}", text))?,
+                );
+            }
+            "House:" => {
+                let link = value
+                    .select(&selectors::LINK)
+                    .next()
+                    .context("House link not found")?;
+                let id = link
+                    .value()
+                    .attr("href")
+                    .and_then(|href| house_id_re.captures(href))
+                    .and_then(|c| c.get(1))
+                    .and_then(|id| id.as_str().parse().ok())
+                    .context(format!("Failed to parse house id in {}", text))?;
+                let captures = house_re
+                    .captures(&text)
+                    .context(format!("Unexpected house format {}", text))?;
+                let town = captures.get(1).context("House town not found")?.as_str();
+                let paid_until = captures
+                    .get(2)
+                    .context("House paid until date not found")?
+                    .as_str();
+
+                // Tibia abbreviates the month ("Sep 08 2023"), but be lenient
+                // in case the full month name is ever used.
+                let paid_until = NaiveDate::parse_from_str(paid_until, "%b %d %Y")
+                    .or_else(|_| NaiveDate::parse_from_str(paid_until, "%B %d %Y"))
+                    .context(format!("Failed to parse house paid until {}", paid_until))?;
+
+                character.houses.push(CharacterHouse {
+                    id,
+                    name: link.text().collect::<String>().sanitize(),
+                    town: town.to_string(),
+                    paid_until,
+                });
+            }
+            "Guild Membership:" => {
+                let name = value
+                    .select(&selectors::LINK)
+                    .next()
+                    .context("Guild link not found")?
+                    .text()
+                    .collect::<String>()
+                    .sanitize();
+                let rank = text
+                    .split(" of the ")
+                    .next()
+                    .context(format!("Guild rank not found in {}", text))?
+                    .to_string();
+
+                character.guild = Some(CharacterGuild { name, rank });
+            }
+            "Last Login:" => {
+                if text.eq_ignore_ascii_case("never logged in") {
+                    character.last_login = None;
+                    continue;
+                }
+                let last_login = last_login_re
+                    .captures(&text)
+                    .and_then(|c| c.get(1))
+                    .context(format!("Last login not found in {}", text))?
+                    .as_str();
+                let naive_dt = NaiveDateTime::parse_from_str(last_login, "%b %d %Y, %H:%M:%S")
+                    .context(format!("Failed to parse last login {}", last_login))?;
+                let utc_time = Stockholm
+                    .from_local_datetime(&naive_dt)
+                    .single()
+                    .context(format!("Ambiguous last login {}", last_login))?
+                    .with_timezone(&Utc);
+
+                character.last_login = Some(utc_time);
+            }
+            "Comment:" => {
+                let comment = value
+                    .text()
+                    .map(|t| t.to_string().sanitize())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                character.comment = Some(comment);
+            }
+            "Account Status:" => {
+                character.account_status = text.parse()?;
+            }
+            header => {
+                tracing::warn!("Skipping unknown character information row {:?}", header);
+            }
+        }
+    }
+
+    // only some characters show their outfit
+    if let Some(outfit_table) = find_table(&main_content, "Outfit") {
+        let (outfit, outfit_url) = parse_outfit(outfit_table)?;
+        character.outfit = Some(outfit);
+        character.outfit_url = Some(outfit_url);
+    }
+
+    Ok(character)
+}
+
+/// Parses the outfit from the parameters of its image url
+#[instrument(skip_all)]
+fn parse_outfit(outfit_table: ElementRef) -> Result<(CharacterOutfit, String), ServerError> {
+    let outfit_url = outfit_table
+        .select(&selectors::IMG)
+        .next()
+        .and_then(|img| img.value().attr("src"))
+        .context("Outfit image not found")?;
+    let url = Url::parse(outfit_url).context(format!("Invalid outfit url {}", outfit_url))?;
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.to_string())
+            .context(format!("Outfit {} not found in {}", name, outfit_url))
+    };
+
+    let outfit = CharacterOutfit {
+        look_type: param("id")?
+            .parse()
+            .context("Failed to parse outfit look type")?,
+        head: param("head")?
+            .parse()
+            .context("Failed to parse outfit head")?,
+        body: param("body")?
+            .parse()
+            .context("Failed to parse outfit body")?,
+        legs: param("legs")?
+            .parse()
+            .context("Failed to parse outfit legs")?,
+        feet: param("feet")?
+            .parse()
+            .context("Failed to parse outfit feet")?,
+        addons: param("addons")?
+            .parse()
+            .context("Failed to parse outfit addons")?,
+        // a mount of 0 means the character isn't riding
+        mount: match param("mount").ok().as_deref() {
+            None | Some("0") => None,
+            Some(mount) => Some(mount.parse().context("Failed to parse outfit mount")?),
+        },
+    };
+
+    Ok((outfit, outfit_url.to_string()))
+}
+
+/// The relative url of a world on this api
+fn world_url(world_name: &str) -> String {
+    format!("/api/v1/worlds/{}", world_name)
+}
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{prelude::*, TimeZone, Utc};
+use chrono_tz::Europe::Stockholm;
+use regex::Regex;
+use reqwest::Response;
+use scraper::{ElementRef, Node};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::characters_character_name::PathParams;
+use crate::{
+    models::{Death, DeathKiller},
+    prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Maximum number of deaths to return
+    #[param(example = 10)]
+    limit: Option<usize>,
+    /// Number of deaths to skip, starting from the most recent one
+    #[param(example = 0)]
+    offset: Option<usize>,
+}
+
+/// Character Deaths
+///
+/// The recent deaths of a character, without the rest of the character information.
+/// Characters without any recent deaths return an empty list.
+#[utoipa::path(
+    get,
+    operation_id = "get_character_deaths",
+    path = "/api/v1/characters/{character_name}/deaths",
+    params(PathParams, QueryParams),
+    responses(
+        (status = 200, description = "Success", body = [Death], example = json!([
+            {
+                "time": "2023-07-07T17:49:44Z",
+                "level": 52,
+                "killers": [
+                    { "type": "player", "name": "Mad Bubble" },
+                    { "type": "creature", "name": "dragon lord" }
+                ]
+            },
+            {
+                "time": "2023-06-30T00:11:05Z",
+                "level": 51,
+                "killers": [{ "type": "creature", "name": "dragon" }]
+            }
+        ])),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Characters"
+)]
+#[instrument(name = "Get Character Deaths", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<Vec<Death>>), ServerError> {
+    let client = &state.client;
+    let character_name = &path_params.character_name;
+
+    let response = client
+        .fetch_character_page(character_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch character page: {:?}", e);
+            e
+        })?;
+    let data_age = DataAge::of(&response);
+    let deaths = parse_character_deaths_page(response, character_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to parse character deaths: {:?}", e);
+            e
+        })?;
+
+    let deaths = deaths
+        .into_iter()
+        .skip(query_params.offset.unwrap_or(0))
+        .take(query_params.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok((data_age, Json(deaths)))
+}
+
+#[instrument(skip(response))]
+async fn parse_character_deaths_page(
+    response: Response,
+    character_name: &str,
+) -> Result<Vec<Death>, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    if find_table(&main_content, "Character Information").is_none() {
+        tracing::info!("Character '{}' not found", character_name);
+        return Err(TibiaError::NotFound)?;
+    }
+
+    parse_character_deaths(&main_content)
+}
+
+/// Parses the "Character Deaths" table of a character page.
+///
+/// Characters without any recent deaths don't have the table at all,
+/// which results in an empty list.
+#[instrument(skip_all)]
+pub fn parse_character_deaths(main_content: &ElementRef) -> Result<Vec<Death>, ServerError> {
+    let Some(table) = find_table(main_content, "Character Deaths") else {
+        return Ok(vec![]);
+    };
+
+    let time_re = Regex::new(r"(.*) CES?T").expect("Invalid death time regex");
+    let description_re = Regex::new(r"^(?:Died|Killed) at Level (\d+) by (.*)\.$")
+        .expect("Invalid death description regex");
+    let separator_re = Regex::new(r", | and ").expect("Invalid killer separator regex");
+    let player_re = Regex::new(r"^\{(\d+)\}$").expect("Invalid player placeholder regex");
+
+    let mut deaths = vec![];
+    for row in table.select(&selectors::TABLE_CONTENT_ROW) {
+        let mut cells = row.select(&selectors::CELL);
+        let (Some(time), Some(description)) = (cells.next(), cells.next()) else {
+            continue;
+        };
+
+        let time_html = time.text().collect::<String>().sanitize();
+        let time = time_re
+            .captures(&time_html)
+            .and_then(|c| c.get(1))
+            .context(format!("Death time not found in {}", time_html))?
+            .as_str();
+        let naive_dt = NaiveDateTime::parse_from_str(time, "%b %d %Y, %H:%M:%S")
+            .context(format!("Failed to parse death time {}", time))?;
+        let time = Stockholm
+            .from_local_datetime(&naive_dt)
+            .single()
+            .context(format!("Ambiguous death time {}", time))?
+            .with_timezone(&Utc);
+
+        // Replace player links with placeholders so that names containing
+        // separators (", " and " and ") can't break up the killers list.
+        // Anything after a line break lists assists and is not part of the kill.
+        let mut players = vec![];
+        let mut text = String::new();
+        for child in description.children() {
+            match child.value() {
+                Node::Text(t) => text.push_str(t),
+                Node::Element(e) if e.name() == "a" => {
+                    let name = ElementRef::wrap(child)
+                        .map(|a| a.text().collect::<String>().sanitize())
+                        .context("Killer link is not an element")?;
+                    text.push_str(&format!("{{{}}}", players.len()));
+                    players.push(name);
+                }
+                Node::Element(e) if e.name() == "br" => break,
+                _ => {}
+            }
+        }
+        let text = text.sanitize();
+
+        let captures = description_re
+            .captures(&text)
+            .context(format!("Unexpected death description {}", text))?;
+        let level = captures
+            .get(1)
+            .and_then(|l| l.as_str().parse().ok())
+            .context(format!("Failed to parse death level in {}", text))?;
+        let killers = captures.get(2).context("Killers not found")?.as_str();
+
+        let killers = separator_re
+            .split(killers)
+            .map(|killer| {
+                let killer = killer.trim();
+                match player_re.captures(killer).and_then(|c| c.get(1)) {
+                    Some(index) => {
+                        let name = index
+                            .as_str()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| players.get(i))
+                            .context(format!("Unknown killer placeholder {}", killer))?;
+                        Ok(DeathKiller::Player { name: name.clone() })
+                    }
+                    None => {
+                        let name = killer
+                            .strip_prefix("an ")
+                            .or_else(|| killer.strip_prefix("a "))
+                            .unwrap_or(killer);
+                        Ok(DeathKiller::Creature {
+                            name: name.to_string(),
+                        })
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        deaths.push(Death {
+            time,
+            level,
+            killers,
+        });
+    }
+
+    Ok(deaths)
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{models::Creature, prelude::*, selectors, utils::data_age::DataAge, AppState};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+pub struct PathParams {
+    /// Name of creature
+    #[param(example = "Dragon Lord")]
+    pub creature_name: String,
+}
+
+/// Creature
+///
+/// Reference information about a creature from the Tibia library.
+#[utoipa::path(
+    get,
+    operation_id = "get_creature",
+    path = "/api/v1/creatures/{creature_name}",
+    params(PathParams),
+    responses(
+        (status = 200, description = "Success", body = Creature, example = json!({
+            "name": "Dragon Lord",
+            "hitpoints": 1900,
+            "experience": 2100,
+            "resistances": {
+                "death": 100,
+                "earth": 20,
+                "energy": 80,
+                "fire": 0,
+                "holy": 100,
+                "ice": 110,
+                "physical": 100
+            },
+            "loot": ["gold coin", "royal helmet", "dragon scale mail"]
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Creatures"
+)]
+#[instrument(name = "Get Creature", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+) -> Result<(DataAge, Json<Creature>), ServerError> {
+    let client = &state.client;
+    let creature_name = &path_params.creature_name;
+
+    let response = client
+        .fetch_creature_page(creature_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch creature page: {:?}", e);
+            e
+        })?;
+    let data_age = DataAge::of(&response);
+    let creature = parse_creature_page(response, creature_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to parse creature page: {:?}", e);
+            e
+        })?;
+
+    Ok((data_age, Json(creature)))
+}
+
+#[instrument(skip(response))]
+async fn parse_creature_page(
+    response: Response,
+    creature_name: &str,
+) -> Result<Creature, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    let Some(information_table) = find_table(&main_content, "Creature Information") else {
+        tracing::info!("Creature '{}' not found", creature_name);
+        return Err(TibiaError::NotFound)?;
+    };
+
+    let mut creature = Creature {
+        name: creature_name.to_string(),
+        hitpoints: 0,
+        experience: 0,
+        resistances: BTreeMap::new(),
+        loot: vec![],
+    };
+
+    for row in information_table.select(&selectors::TABLE_CONTENT_ROW) {
+        let mut cells = row.select(&selectors::CELL);
+        let (Some(header), Some(value)) = (cells.next(), cells.next()) else {
+            continue;
+        };
+        let text = value.text().collect::<String>().sanitize();
+
+        match header.text().collect::<String>().sanitize().as_str() {
+            "Name:" => {
+                creature.name = text;
+            }
+            "Hitpoints:" => {
+                creature.hitpoints = text
+                    .replace(',', "")
+                    .parse()
+                    .context(format!("Failed to parse hitpoints {}", text))?;
+            }
+            "Experience:" => {
+                creature.experience = text
+                    .replace(',', "")
+                    .parse()
+                    .context(format!("Failed to parse experience {}", text))?;
+            }
+            header => {
+                tracing::warn!("Skipping unknown creature information row {:?}", header);
+            }
+        }
+    }
+
+    if let Some(resistances_table) = find_table(&main_content, "Resistances") {
+        for row in resistances_table.select(&selectors::TABLE_CONTENT_ROW) {
+            let mut cells = row.select(&selectors::CELL);
+            let (Some(element), Some(value)) = (cells.next(), cells.next()) else {
+                continue;
+            };
+            let element = element.text().collect::<String>().sanitize();
+            let element = element.trim_end_matches(':').to_lowercase();
+            let value = value.text().collect::<String>().sanitize();
+            let percentage = value
+                .trim_end_matches('%')
+                .parse()
+                .context(format!("Failed to parse {} resistance {}", element, value))?;
+
+            creature.resistances.insert(element, percentage);
+        }
+    }
+
+    // creatures without any loot don't have the table at all
+    if let Some(loot_table) = find_table(&main_content, "Loot") {
+        creature.loot = loot_table
+            .select(&selectors::TABLE_CONTENT_ROW)
+            .map(|row| row.text().collect::<String>().sanitize())
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+
+    Ok(creature)
+}
@@ -0,0 +1,8 @@
+use axum::response::IntoResponse;
+
+use crate::prelude::ClientError;
+
+/// Answers requests for unknown routes like requests for missing resources
+pub async fn get() -> impl IntoResponse {
+    ClientError::not_found()
+}
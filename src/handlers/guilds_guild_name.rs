@@ -0,0 +1,365 @@
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::NaiveDate;
+use regex::Regex;
+use reqwest::Response;
+use scraper::ElementRef;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    models::{GuildDetails, GuildMember, GuildWar, PaginatedGuildDetails},
+    prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query, parsing::absolute_url},
+    AppState,
+};
+
+/// The number of members per page unless requested otherwise
+pub const DEFAULT_PER_PAGE: u32 = 50;
+/// The maximum number of members that can be requested per page
+pub const MAX_PER_PAGE: u32 = 100;
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+pub struct PathParams {
+    /// Name of guild
+    #[param(example = "Red Rose")]
+    pub guild_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// The page of members to return, starting from 1
+    #[param(example = 1)]
+    page: Option<u32>,
+    /// The number of members per page (at most 100)
+    #[param(example = 50)]
+    per_page: Option<u32>,
+    /// Only include members with this rank
+    #[serde(alias = "has_rank")]
+    #[param(example = "Vice Leader")]
+    has_rank: Option<String>,
+}
+
+impl QueryParams {
+    /// The requested page and number of members per page
+    pub fn pagination(&self) -> Result<(u32, u32), ServerError> {
+        let page = self.page.unwrap_or(1);
+        if page < 1 {
+            return Err(ServerError::InvalidParameter(
+                "page must be at least 1".to_string(),
+            ));
+        }
+        let per_page = self.per_page.unwrap_or(DEFAULT_PER_PAGE);
+        if !(1..=MAX_PER_PAGE).contains(&per_page) {
+            return Err(ServerError::InvalidParameter(format!(
+                "perPage must be between 1 and {MAX_PER_PAGE}"
+            )));
+        }
+
+        Ok((page, per_page))
+    }
+
+    /// Picks out the requested page of the members with the requested rank,
+    /// tibia.com lists every member at once
+    pub fn paginate(
+        &self,
+        mut guild: GuildDetails,
+        page: u32,
+        per_page: u32,
+    ) -> PaginatedGuildDetails {
+        if let Some(rank) = &self.has_rank {
+            guild.members.retain(|member| &member.rank == rank);
+        }
+
+        PaginatedGuildDetails::paginate(guild, page, per_page)
+    }
+}
+
+/// Guild
+///
+/// The guild details, with its members paginated.
+#[utoipa::path(
+    get,
+    operation_id = "get_guild",
+    path = "/api/v1/guilds/{guild_name}",
+    params(PathParams, QueryParams),
+    responses(
+        (status = 200, description = "Success", body = PaginatedGuildDetails, example = json!({
+            "name": "Blood Oath",
+            "tibiaUrl": "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Blood%20Oath",
+            "logo": "https://static.tibia.com/images/community/default_logo.gif",
+            "description": "We will not yield.",
+            "applicationOpen": false,
+            "createdAt": "2018-02-11",
+            "founder": "Hälge",
+            "memberCount": 1,
+            "members": {
+                "data": [
+                    {
+                        "name": "Hälge",
+                        "rank": "Leader",
+                        "vocation": "royalPaladin",
+                        "level": 310,
+                        "joined": "2018-02-11",
+                        "isOnline": true
+                    }
+                ],
+                "page": 1,
+                "perPage": 50,
+                "totalItems": 1,
+                "totalPages": 1
+            },
+            "wars": [
+                {
+                    "opponentGuild": "Red Rose",
+                    "killsUs": 9,
+                    "killsThem": 14,
+                    "status": "war"
+                }
+            ]
+        })),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "perPage must be between 1 and 100"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Guilds"
+)]
+#[instrument(name = "Get Guild", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<PaginatedGuildDetails>), ServerError> {
+    let client = &state.client;
+    let (page, per_page) = query_params.pagination()?;
+
+    // the guild page doesn't tell the world, so there is no url on this api to link to
+    let (data_age, guild) = get_guild(client, &path_params.guild_name, None).await?;
+
+    Ok((data_age, Json(query_params.paginate(guild, page, per_page))))
+}
+
+/// The details of a guild, linked to its url on this api when its world is known
+#[instrument(skip(client))]
+pub async fn get_guild<S: Client>(
+    client: &S,
+    guild_name: &str,
+    world_name: Option<&str>,
+) -> Result<(DataAge, GuildDetails), ServerError> {
+    let response = client.fetch_guild_page(guild_name).await.map_err(|e| {
+        tracing::error!("Failed to fetch guild page for {guild_name}: {:?}", e);
+        e
+    })?;
+    let data_age = DataAge::of(&response);
+    let guild = parse_guild_page(response, guild_name, world_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to parse guild page for {guild_name}: {:?}", e);
+            e
+        })?;
+
+    Ok((data_age, guild))
+}
+
+#[instrument(skip(response))]
+async fn parse_guild_page(
+    response: Response,
+    guild_name: &str,
+    world_name: Option<&str>,
+) -> Result<GuildDetails, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    let Some(information_table) = find_table(&main_content, "Guild Information") else {
+        tracing::info!("Guild '{}' not found", guild_name);
+        return Err(TibiaError::NotFound)?;
+    };
+
+    let information = information_table
+        .select(&selectors::GUILD_INFORMATION)
+        .next()
+        .context("Guild information not found")?;
+    let founded_re =
+        Regex::new(r"^The .+ guild was founded on (.+) by (.+)\.$").expect("Invalid founded regex");
+    let lines = information
+        .text()
+        .map(|t| t.to_string().sanitize())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>();
+
+    // the description is optional, and always followed by the founding date
+    let description = lines.first().filter(|t| !founded_re.is_match(t)).cloned();
+    let (created_at, founder) = lines
+        .iter()
+        .find_map(|t| founded_re.captures(t))
+        .map(|captures| {
+            let created_at = NaiveDate::parse_from_str(&captures[1], "%B %d, %Y").context(
+                format!("Failed to parse guild founding date {}", &captures[1]),
+            )?;
+            anyhow::Ok((created_at, captures[2].to_string()))
+        })
+        .context("Guild founding date not found")??;
+    let application_open = lines.iter().any(|t| t.contains("opened for applications"));
+    let logo = information_table
+        .select(&selectors::IMG)
+        .next()
+        .and_then(|img| img.value().attr("src"))
+        .map(absolute_url);
+
+    let members = match find_table(&main_content, "Guild Members") {
+        Some(members_table) => parse_members(members_table)?,
+        None => vec![],
+    };
+    // The wars table is only shown while the guild is involved in a war
+    let wars = match find_table(&main_content, "Guild Wars") {
+        Some(wars_table) => parse_wars(wars_table)?,
+        None => vec![],
+    };
+
+    Ok(GuildDetails {
+        name: guild_name.to_string(),
+        guild_url: world_name.map(|world_name| guild_url(world_name, guild_name)),
+        tibia_url: tibia_url(guild_name),
+        logo,
+        description,
+        application_open,
+        created_at,
+        founder,
+        member_count: members.len() as u32,
+        members,
+        wars,
+    })
+}
+
+#[instrument(skip_all)]
+fn parse_members(members_table: ElementRef) -> Result<Vec<GuildMember>, ServerError> {
+    let mut members = vec![];
+    // tibia only shows the rank on the first member of each rank
+    let mut rank = String::new();
+    for row in members_table.select(&selectors::TABLE_CONTENT_DATA_ROW) {
+        let cells = row.select(&selectors::CELL).collect::<Vec<_>>();
+        let [rank_cell, name_cell, vocation, level, joined, status] = cells.as_slice() else {
+            log_unexpected_html("Unexpected guild member row", &row);
+            return Err(anyhow::anyhow!("Unexpected guild member row"))?;
+        };
+
+        let rank_text = rank_cell.text().collect::<String>().sanitize();
+        if !rank_text.is_empty() {
+            rank = rank_text;
+        }
+
+        let name = name_cell
+            .select(&selectors::LINK)
+            .next()
+            .context("Guild member link not found")?
+            .text()
+            .collect::<String>()
+            .sanitize();
+        // the title follows the name in parentheses
+        let title = name_cell
+            .text()
+            .skip(1)
+            .collect::<String>()
+            .sanitize()
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .to_string();
+
+        let vocation = match vocation.text().collect::<String>().sanitize().as_str() {
+            "None" => None,
+            v => Some(v.parse()?),
+        };
+        let level = level.text().collect::<String>().sanitize();
+        let joined = joined.text().collect::<String>().sanitize();
+
+        members.push(GuildMember {
+            name,
+            title: Some(title).filter(|t| !t.is_empty()),
+            rank: rank.clone(),
+            vocation,
+            level: level
+                .parse()
+                .context(format!("Failed to parse guild member level {}", level))?,
+            joined: NaiveDate::parse_from_str(&joined, "%b %d %Y").context(format!(
+                "Failed to parse guild member joining date {}",
+                joined
+            ))?,
+            is_online: status.text().collect::<String>().sanitize() == "online",
+        });
+    }
+
+    Ok(members)
+}
+
+#[instrument(skip_all)]
+fn parse_wars(wars_table: ElementRef) -> Result<Vec<GuildWar>, ServerError> {
+    let mut wars = vec![];
+    for row in wars_table.select(&selectors::TABLE_CONTENT_DATA_ROW) {
+        let cells = row
+            .select(&selectors::CELL)
+            .map(|c| c.text().collect::<String>().sanitize())
+            .collect::<Vec<_>>();
+        let [opponent_guild, kills, deaths, status] = cells.as_slice() else {
+            log_unexpected_html("Unexpected guild war row", &row);
+            return Err(anyhow::anyhow!("Unexpected guild war row"))?;
+        };
+
+        wars.push(GuildWar {
+            opponent_guild: opponent_guild.to_string(),
+            kills_us: deaths
+                .parse()
+                .context(format!("Failed to parse guild war deaths {}", deaths))?,
+            kills_them: kills
+                .parse()
+                .context(format!("Failed to parse guild war kills {}", kills))?,
+            status: status.parse()?,
+        });
+    }
+
+    Ok(wars)
+}
+
+/// The relative url of a guild on this api
+pub fn guild_url(world_name: &str, guild_name: &str) -> String {
+    format!(
+        "/api/v1/worlds/{}/guilds/{}",
+        urlencoding::encode(world_name),
+        urlencoding::encode(guild_name)
+    )
+}
+
+/// The url of a guild on tibia.com
+pub fn tibia_url(guild_name: &str) -> String {
+    format!(
+        "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName={}",
+        urlencoding::encode(guild_name)
+    )
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::openapi::{VersionedOpenApiDocs, LATEST_VERSION};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Index {
+    pub endpoints: Vec<Endpoint>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Endpoint {
+    /// The http method, such as `GET`
+    pub method: String,
+    /// The path with its parameters in braces, such as `/api/v1/worlds/{world_name}`
+    pub path: String,
+    /// The summary of the operation in the docs
+    pub description: Option<String>,
+}
+
+/// Lists the documented endpoints, taken from the docs so that it stays in
+/// sync with the routes
+pub async fn get(State(openapi_docs): State<Arc<VersionedOpenApiDocs>>) -> Json<Index> {
+    let endpoints = openapi_docs[LATEST_VERSION]
+        .paths
+        .paths
+        .iter()
+        .flat_map(|(path, item)| {
+            item.operations
+                .iter()
+                .map(move |(method, operation)| Endpoint {
+                    // the methods are serialized lowercase, like `get`
+                    method: serde_json::to_value(method)
+                        .ok()
+                        .and_then(|method| method.as_str().map(str::to_uppercase))
+                        .unwrap_or_default(),
+                    path: path.clone(),
+                    description: operation.summary.clone(),
+                })
+        })
+        .collect();
+
+    Json(Index { endpoints })
+}
@@ -1,18 +1,66 @@
+pub mod fallback;
 pub mod redocly;
 
 // Handlers
 
 /// /__healthcheck
 pub mod __healthcheck;
+/// /__version
+pub mod __version;
+/// /bazaar
+pub mod bazaar;
+/// /characters
+pub mod characters;
+/// /characters/:character_name
+pub mod characters_character_name;
+/// /characters/:character_name/deaths
+pub mod characters_character_name_deaths;
+/// /creatures/:creature_name
+pub mod creatures_creature_name;
+/// /guilds/:guild_name
+pub mod guilds_guild_name;
+/// /
+pub mod index;
+/// /news.rss
+pub mod news_rss;
+/// /residences/auctions
+pub mod residences_auctions;
+/// /server-save
+pub mod server_save;
+/// /spells
+pub mod spells;
 /// /towns
 pub mod towns;
 /// /worlds
 pub mod worlds;
+/// /worlds/compare
+pub mod worlds_compare;
 /// /worlds/:world_name
 pub mod worlds_world_name;
 /// /worlds/:world_name/guilds
 pub mod worlds_world_name_guilds;
+/// /worlds/:world_name/guilds/:guild_name
+pub mod worlds_world_name_guilds_guild_name;
+/// /worlds/:world_name/guilds/:guild_name/members
+pub mod worlds_world_name_guilds_guild_name_members;
+/// /worlds/:world_name/highscores/:category
+pub mod worlds_world_name_highscores_category;
 /// /worlds/:world_name/kill-statistics
 pub mod worlds_world_name_kill_statistics;
+/// /worlds/:world_name/kill-statistics/:race
+pub mod worlds_world_name_kill_statistics_race;
+/// /worlds/:world_name/online-players
+pub mod worlds_world_name_online_players;
+/// /worlds/:world_name/players-online/record
+pub mod worlds_world_name_players_online_record;
+/// /worlds/:world_name/quest-titles
+pub mod worlds_world_name_quest_titles;
 /// /worlds/:world_name/residences
 pub mod worlds_world_name_residences;
+/// /worlds/:world_name/summary
+pub mod worlds_world_name_summary;
+
+// WebSocket handlers
+
+/// /ws/v1/worlds/:world_name/players-online
+pub mod ws_worlds_world_name_players_online;
@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response as AxumResponse},
+};
+use chrono::{NaiveDate, TimeZone};
+use chrono_tz::Europe::Stockholm;
+use regex::Regex;
+use reqwest::Response;
+use rss::{CategoryBuilder, ChannelBuilder, GuidBuilder, ItemBuilder};
+use scraper::ElementRef;
+use tracing::instrument;
+
+use crate::{models::News, prelude::*, selectors, utils::data_age::DataAge, AppState};
+
+/// The page every news item is on, for the items without an article of their own
+const LATEST_NEWS_URL: &str = "https://www.tibia.com/news/?subtopic=latestnews";
+
+/// News RSS
+///
+/// The latest news from tibia.com as an RSS 2.0 feed.
+#[utoipa::path(
+    get,
+    operation_id = "get_news_rss",
+    path = "/api/v1/news.rss",
+    responses(
+        (status = 200, description = "Success", content_type = "application/rss+xml", body = String),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "News"
+)]
+#[instrument(name = "Get News RSS", skip(state))]
+pub async fn get<S: Client>(State(state): State<AppState<S>>) -> Result<AxumResponse, ServerError> {
+    let client = &state.client;
+
+    let response = client.fetch_news_page().await.map_err(|e| {
+        tracing::error!("Failed to fetch news page: {:?}", e);
+        e
+    })?;
+    let data_age = DataAge::of(&response);
+    let news = parse_news_page(response).await.map_err(|e| {
+        tracing::error!("Failed to parse news page: {:?}", e);
+        e
+    })?;
+
+    let items = news
+        .into_iter()
+        .map(|news| {
+            // tibia only shows the date, so use the start of the day in server time
+            let pub_date = news
+                .date
+                .and_hms_opt(0, 0, 0)
+                .and_then(|dt| Stockholm.from_local_datetime(&dt).single())
+                .map(|dt| dt.to_rfc2822());
+
+            ItemBuilder::default()
+                .title(news.title)
+                .link(
+                    news.url
+                        .clone()
+                        .unwrap_or_else(|| LATEST_NEWS_URL.to_string()),
+                )
+                .guid(
+                    news.url
+                        .map(|url| GuidBuilder::default().value(url).permalink(true).build()),
+                )
+                .pub_date(pub_date)
+                .category(
+                    CategoryBuilder::default()
+                        .name(news.category.title())
+                        .build(),
+                )
+                .description(news.teaser)
+                .build()
+        })
+        .collect::<Vec<_>>();
+
+    let channel = ChannelBuilder::default()
+        .title("Tibia News")
+        .link(LATEST_NEWS_URL)
+        .description("The latest news from tibia.com")
+        .language("en".to_string())
+        .items(items)
+        .build();
+
+    Ok((
+        data_age,
+        [(header::CONTENT_TYPE, "application/rss+xml")],
+        channel.to_string(),
+    )
+        .into_response())
+}
+
+#[instrument(skip(response))]
+async fn parse_news_page(response: Response) -> Result<Vec<News>, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    let category_re = Regex::new(r"newsicon_(\w+?)_(?:big|small)").expect("Invalid icon regex");
+    let id_re = Regex::new(r"subtopic=newsarchive&id=(\d+)").expect("Invalid news id regex");
+
+    let mut news = vec![];
+    for headline in main_content.select(&selectors::NEWS_HEADLINE) {
+        let title = headline
+            .select(&selectors::NEWS_HEADLINE_TEXT)
+            .next()
+            .context("News title not found")?
+            .text()
+            .collect::<String>()
+            .sanitize();
+        let category = headline
+            .select(&selectors::NEWS_HEADLINE_ICON)
+            .next()
+            .and_then(|icon| icon.value().attr("src"))
+            .and_then(|src| category_re.captures(src))
+            .and_then(|c| c.get(1))
+            .context(format!("News category not found for {}", title))?
+            .as_str()
+            .parse()?;
+        let date = headline
+            .select(&selectors::NEWS_HEADLINE_DATE)
+            .next()
+            .context("News date not found")?
+            .text()
+            .collect::<String>()
+            .sanitize();
+        let date = date.trim_end_matches('-').trim();
+        let date = NaiveDate::parse_from_str(date, "%b %d %Y")
+            .context(format!("Failed to parse news date {}", date))?;
+
+        // every headline is followed by the table with its content
+        let table = headline
+            .next_siblings()
+            .find_map(ElementRef::wrap)
+            .context(format!("News content not found for {}", title))?;
+        let teaser = table
+            .select(&selectors::PARAGRAPH)
+            .map(|p| p.text().collect::<String>().sanitize())
+            .find(|p| !p.is_empty())
+            .unwrap_or_default();
+        // only articles too long for the page link to the archive with a "read more"
+        let url = table
+            .select(&selectors::LINK)
+            .filter_map(|a| a.value().attr("href"))
+            .find(|href| id_re.is_match(href));
+        let id = url
+            .and_then(|url| id_re.captures(url))
+            .and_then(|c| c.get(1))
+            .and_then(|id| id.as_str().parse().ok());
+
+        news.push(News {
+            id,
+            title,
+            category,
+            date,
+            teaser,
+            url: url.map(|url| url.to_string()),
+        });
+    }
+
+    Ok(news)
+}
@@ -1,17 +1,39 @@
+use std::sync::Arc;
+
 use axum::{
-    extract::State,
-    response::{Html, IntoResponse, Redirect},
+    extract::{Path, State},
+    response::{Html, IntoResponse, Redirect, Response},
     Json,
 };
-use utoipa::openapi::OpenApi;
 use utoipa_redoc::Redoc;
 
+use crate::{
+    prelude::ClientError,
+    utils::openapi::{VersionedOpenApiDocs, LATEST_VERSION},
+};
+
 pub async fn redirect_redocly() -> Redirect {
     Redirect::temporary("/api-docs")
 }
 
-pub async fn serve_openapi(State(openapi_docs): State<OpenApi>) -> impl IntoResponse {
-    Json(openapi_docs)
+/// Serves the docs of the latest version
+pub async fn serve_openapi(State(openapi_docs): State<Arc<VersionedOpenApiDocs>>) -> Response {
+    Json(&openapi_docs[LATEST_VERSION]).into_response()
+}
+
+/// Serves the docs of a version by its file name, e.g. `v1.json`
+pub async fn serve_versioned_openapi(
+    State(openapi_docs): State<Arc<VersionedOpenApiDocs>>,
+    Path(file_name): Path<String>,
+) -> Response {
+    let openapi = file_name
+        .strip_suffix(".json")
+        .and_then(|version| openapi_docs.get(version));
+
+    match openapi {
+        Some(openapi) => Json(openapi).into_response(),
+        None => ClientError::not_found().into_response(),
+    }
 }
 
 pub async fn serve_redocly() -> impl IntoResponse {
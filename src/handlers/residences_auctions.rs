@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use axum::{extract::State, Json};
+use capitalize::Capitalize;
+use futures::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::worlds_world_name_residences::get_residences;
+use crate::{
+    models::{Residence, ResidenceType},
+    prelude::*,
+    utils::{data_age::DataAge, extract::Query, parsing::to_title_case},
+    AppState,
+};
+
+/// The maximum number of worlds that can be scanned at once
+pub const MAX_SCANNED_WORLDS: usize = 5;
+/// The number of worlds scanned in parallel, each fetching up to 10 pages at a time
+const CONCURRENT_WORLDS: usize = 2;
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Comma separated names of the worlds to scan (1 to 5 worlds)
+    #[param(example = "Antica,Secura")]
+    worlds: String,
+    /// Only scan this town
+    #[param(example = "Thais")]
+    town: Option<String>,
+    /// Only scan residences of this type (`house` or `guildhall`)
+    #[serde(rename = "type")]
+    residence_type: Option<ResidenceType>,
+}
+
+impl QueryParams {
+    /// The names of the worlds to scan, each listed once
+    pub fn world_names(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.worlds
+            .split(',')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(|name| name.capitalize())
+            .filter(|name| seen.insert(name.clone()))
+            .collect()
+    }
+}
+
+/// Residence Auctions
+///
+/// The residences currently auctioned on several worlds, ending first. Auctions
+/// without bids have no known end and are listed last.
+///
+/// Every world needs a page per town and residence type from tibia.com, so
+/// scanning every town of 5 worlds takes up to 140 requests, which can take
+/// tens of seconds unless the pages are cached. Pass `town` or `type` to
+/// scan fewer pages.
+#[utoipa::path(
+    get,
+    operation_id = "get_residence_auctions",
+    path = "/api/v1/residences/auctions",
+    params(QueryParams),
+    responses(
+        (status = 200, description = "Success", body = [Residence], example = json!([
+            {
+                "id": 50102,
+                "world": "Jaguna",
+                "town": "Edron",
+                "type": "house",
+                "name": "Castle Shop 2",
+                "size": 42,
+                "rent": 400000,
+                "status": {
+                    "type": "auctionWithBid",
+                    "bid": 1555555,
+                    "expiryTime": "2023-09-18T07:00:00Z"
+                }
+            },
+            {
+                "id": 50201,
+                "world": "Jaguna",
+                "town": "Edron",
+                "type": "house",
+                "name": "Castle Street 1",
+                "size": 60,
+                "rent": 300000,
+                "status": { "type": "auctionNoBid" }
+            }
+        ])),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "Between 1 and 5 worlds can be scanned at once"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get Residence Auctions", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<Vec<Residence>>), ServerError> {
+    let client = &state.client;
+    let world_names = query_params.world_names();
+
+    if !(1..=MAX_SCANNED_WORLDS).contains(&world_names.len()) {
+        return Err(ServerError::InvalidParameter(format!(
+            "Between 1 and {MAX_SCANNED_WORLDS} worlds can be scanned at once"
+        )));
+    }
+    for world_name in &world_names {
+        state.ensure_world_exists(world_name)?;
+    }
+
+    let towns = match query_params.town.as_deref() {
+        Some(town) => vec![to_title_case(town)],
+        None => state.towns.lock().unwrap().clone(),
+    };
+    // the towns are loaded on startup, without them there is nothing to scan
+    if towns.is_empty() {
+        tracing::warn!("No towns loaded, skipping residence auctions");
+        return Err(anyhow::anyhow!("No towns loaded"))?;
+    }
+    let residence_types = query_params
+        .residence_type
+        .map(|t| vec![t])
+        .unwrap_or(vec![ResidenceType::House, ResidenceType::Guildhall]);
+
+    let towns = &towns;
+    let residence_types = &residence_types;
+    let results = futures::stream::iter(world_names)
+        .map(|world_name| async move {
+            get_residences(client, &world_name, towns, residence_types).await
+        })
+        .buffer_unordered(CONCURRENT_WORLDS)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut data_age = DataAge::now();
+    let mut auctions = vec![];
+    for result in results {
+        let (world_data_age, residences) = result?;
+        data_age = data_age.oldest(world_data_age);
+        auctions.extend(
+            residences
+                .into_iter()
+                .filter(|residence| residence.status.is_active_auction()),
+        );
+    }
+    // auctions without bids have no expiry time and go last
+    auctions.sort_by_key(|residence| {
+        let expiry_time = residence.status.expiry_time();
+        (expiry_time.is_none(), expiry_time)
+    });
+
+    Ok((data_age, Json(auctions)))
+}
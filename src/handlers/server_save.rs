@@ -0,0 +1,29 @@
+use axum::Json;
+use chrono::Utc;
+
+use crate::{models::ServerSave, prelude::*};
+
+/// Server Save
+///
+/// The time of the next daily server save, at 10:00 in the time zone of tibia.com.
+#[utoipa::path(
+    get,
+    operation_id = "get_server_save",
+    path = "/api/v1/server-save",
+    responses(
+        (status = 200, description = "Success", body = ServerSave, example = json!({
+            "nextServerSave": "2023-07-01T08:00:00Z",
+            "secondsUntil": 3600
+        })),
+    ),
+    tag = "Server Save"
+)]
+pub async fn get() -> Json<ServerSave> {
+    let now = Utc::now();
+    let next_server_save = next_server_save(now);
+
+    Json(ServerSave {
+        next_server_save,
+        seconds_until: (next_server_save - now).num_seconds(),
+    })
+}
@@ -0,0 +1,169 @@
+use anyhow::{Context, Result};
+use axum::{extract::State, Json};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    models::{Spell, SpellType, Vocation},
+    prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Only include spells that can be learned by this vocation
+    vocation: Option<Vocation>,
+    /// Only include spells of this type
+    #[serde(rename = "type")]
+    spell_type: Option<SpellType>,
+}
+
+/// Spells
+///
+#[utoipa::path(
+    get,
+    operation_id = "get_spells",
+    path = "/api/v1/spells",
+    params(QueryParams),
+    responses(
+        (status = 200, description = "Success", body = [Spell], example = json!([
+            {
+                "name": "Light Healing",
+                "formula": "exura",
+                "vocations": ["druid", "paladin", "sorcerer"],
+                "type": "instant",
+                "level": 8,
+                "mana": 20,
+                "price": 0,
+                "cities": ["Ab'Dendriel", "Carlin", "Thais", "Venore"]
+            },
+            {
+                "name": "Ultimate Healing Rune",
+                "formula": "adura vita",
+                "vocations": ["druid"],
+                "type": "rune",
+                "level": 24,
+                "magicLevel": 4,
+                "mana": 400,
+                "soul": 3,
+                "price": 1500,
+                "cities": ["Ab'Dendriel", "Carlin"]
+            }
+        ])),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "Invalid value for parameter 'type': expected one of instant, rune"
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Spells"
+)]
+#[instrument(name = "Get Spells", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<Vec<Spell>>), ServerError> {
+    let client = &state.client;
+
+    let response = client.fetch_spells_page().await.map_err(|e| {
+        tracing::error!("Failed to fetch spells page: {:?}", e);
+        e
+    })?;
+    let data_age = DataAge::of(&response);
+    let spells = parse_spells_page(response).await.map_err(|e| {
+        tracing::error!("Failed to parse spells page: {:?}", e);
+        e
+    })?;
+
+    let spells = spells
+        .into_iter()
+        .filter(|spell| {
+            // promoted vocations can learn the spells of their base vocation
+            let Some(vocation) = query_params.vocation else {
+                return true;
+            };
+            spell.vocations.contains(&vocation.base())
+        })
+        .filter(|spell| {
+            let Some(spell_type) = query_params.spell_type else {
+                return true;
+            };
+            spell.spell_type == spell_type
+        })
+        .collect();
+
+    Ok((data_age, Json(spells)))
+}
+
+#[instrument(skip(response))]
+async fn parse_spells_page(response: Response) -> Result<Vec<Spell>, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    let table = find_table(&main_content, "Spells").context("Spells table not found")?;
+
+    let mut spells = vec![];
+    for row in table.select(&selectors::TABLE_CONTENT_DATA_ROW) {
+        let cells = row
+            .select(&selectors::CELL)
+            .map(|c| c.text().collect::<String>().sanitize())
+            .collect::<Vec<_>>();
+        let [name, formula, vocations, spell_type, level, magic_level, mana, soul, price, cities] =
+            cells.as_slice()
+        else {
+            return Err(anyhow::anyhow!("Unexpected spell row {:?}", cells))?;
+        };
+
+        let vocations = vocations
+            .split(", ")
+            .map(|v| v.parse())
+            .collect::<Result<Vec<Vocation>>>()?;
+
+        spells.push(Spell {
+            name: name.to_string(),
+            formula: formula.to_string(),
+            vocations,
+            spell_type: spell_type.parse()?,
+            level: parse_number(level)?.context("Spell level not found")?,
+            magic_level: parse_number(magic_level)?,
+            mana: parse_number(mana)?,
+            soul: parse_number(soul)?,
+            price: parse_number(price)?.unwrap_or(0),
+            cities: cities.split(", ").map(|c| c.to_string()).collect(),
+        });
+    }
+
+    Ok(spells)
+}
+
+/// Parses a number column, where a dash means the value doesn't apply
+fn parse_number<T: std::str::FromStr>(text: &str) -> Result<Option<T>> {
+    match text {
+        "-" | "" => Ok(None),
+        text => text
+            .replace(',', "")
+            .parse()
+            .map(Some)
+            .map_err(|_| anyhow::anyhow!("Failed to parse number {}", text)),
+    }
+}
@@ -1,10 +1,9 @@
 use crate::prelude::*;
 use anyhow::{Context, Result};
 use axum::{extract::State, Json};
-use scraper::Selector;
 use tracing::instrument;
 
-use crate::AppState;
+use crate::{selectors, utils::data_age::DataAge, AppState};
 
 /// Towns
 ///
@@ -41,13 +40,14 @@ use crate::AppState;
 #[instrument(name = "Get Towns", skip(state))]
 pub async fn get<S: Client>(
     State(state): State<AppState<S>>,
-) -> Result<Json<Vec<String>>, ServerError> {
+) -> Result<(DataAge, Json<Vec<String>>), ServerError> {
     let client = &state.client;
 
     let page = client.fetch_towns_page().await.map_err(|e| {
         tracing::error!("Failed to fetch towns page: {:?}", e);
         e
     })?;
+    let data_age = DataAge::of(&page);
 
     let towns = parse_towns_page(page).await.map_err(|e| {
         tracing::error!("Failed to parse towns page: {:?}", e);
@@ -61,7 +61,7 @@ pub async fn get<S: Client>(
         Err(_poisoned) => Err(anyhow::anyhow!("Mutex poisoned"))?,
     }
 
-    Ok(Json(towns))
+    Ok((data_age, Json(towns)))
 }
 
 #[instrument(skip(page))]
@@ -69,9 +69,8 @@ async fn parse_towns_page(page: reqwest::Response) -> Result<Vec<String>, Server
     let text = page.text().await?;
     let document = scraper::Html::parse_document(&text);
 
-    let title_selector = Selector::parse("title").expect("Invalid selector for title");
     let title = document
-        .select(&title_selector)
+        .select(&selectors::TITLE)
         .next()
         .and_then(|t| t.text().next())
         .unwrap_or_default();
@@ -80,23 +79,18 @@ async fn parse_towns_page(page: reqwest::Response) -> Result<Vec<String>, Server
         return Err(TibiaError::Maintenance)?;
     };
 
-    let selector = Selector::parse(".main-content").expect("Invalid selector for main content");
     let main_content = &document
-        .select(&selector)
+        .select(&selectors::MAIN_CONTENT)
         .next()
         .context("ElementRef for main content not found")?;
 
-    let tables_selector =
-        Selector::parse("#houses table.TableContent").expect("Invalid selector for towns table");
     let table = main_content
-        .select(&tables_selector)
+        .select(&selectors::TOWNS_TABLE)
         .last()
         .context("Towns table not found")?;
 
-    let towns_selector =
-        Selector::parse("input[name=town]").expect("Invalid selector for towns row");
     let towns = table
-        .select(&towns_selector)
+        .select(&selectors::TOWN_INPUT)
         .map(|e| e.value().attr("value"))
         .collect::<Option<Vec<_>>>()
         .context("Failed to parse towns")?;
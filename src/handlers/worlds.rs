@@ -1,26 +1,134 @@
+use std::collections::HashSet;
+
 use anyhow::{Context, Result};
 use axum::{extract::State, Json};
-use chrono::{prelude::*, TimeZone, Utc};
+use chrono::{prelude::*, Duration, TimeZone, Utc};
 use chrono_tz::Europe::Stockholm;
+use futures::stream::StreamExt;
 use regex::Regex;
 use reqwest::Response;
-use scraper::Selector;
+use scraper::ElementRef;
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
+use super::worlds_world_name::{get_world_details, tibia_url};
 use crate::{
-    models::{GameWorldType, TransferType, World, WorldsResponse},
+    models::{
+        BattlEyeType, GameWorldType, TransferType, World, WorldSort, WorldsDelta, WorldsResponse,
+    },
     prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query},
     AppState,
 };
 
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Include worlds that are currently offline (defaults to `false`)
+    #[serde(alias = "include_offline")]
+    #[param(example = false)]
+    include_offline: Option<bool>,
+    /// Include the change since the previous fetch (defaults to `false`)
+    #[serde(alias = "include_delta")]
+    #[param(example = false)]
+    include_delta: Option<bool>,
+    /// Include the online record of each world, which requires fetching the
+    /// details of every world (defaults to `false`)
+    #[serde(alias = "include_records")]
+    #[param(example = false)]
+    include_records: Option<bool>,
+    /// The order of the worlds (defaults to the order of tibia.com)
+    sort: Option<WorldSort>,
+}
+
+/// The totals of the previous fetch, to compute `deltaSinceLastFetch` from
+#[derive(Debug, Clone, Copy)]
+pub struct WorldsSnapshot {
+    players_online_total: u32,
+    record_players: u32,
+}
+
+/// How long the listed worlds are trusted to reject requests for unknown worlds
+const KNOWN_WORLDS_MAX_AGE: Duration = Duration::hours(1);
+
+/// The names of the worlds listed on tibia.com, to answer requests for
+/// unknown worlds without fetching them
+#[derive(Debug, Clone)]
+pub struct KnownWorlds {
+    /// The lowercased world names
+    names: HashSet<String>,
+    fetched_at: DateTime<Utc>,
+}
+
+impl KnownWorlds {
+    pub fn new(worlds: &[World], fetched_at: DateTime<Utc>) -> Self {
+        Self {
+            names: worlds.iter().map(|w| w.name.to_lowercase()).collect(),
+            fetched_at,
+        }
+    }
+
+    /// Whether the world is missing from a recent list of worlds. Empty or
+    /// stale lists know nothing, so that new worlds are still fetched.
+    pub fn is_unknown(&self, world_name: &str, now: DateTime<Utc>) -> bool {
+        let is_fresh = now - self.fetched_at < KNOWN_WORLDS_MAX_AGE;
+
+        is_fresh && !self.names.is_empty() && !self.names.contains(&world_name.to_lowercase())
+    }
+}
+
 /// Worlds
 ///
+/// **Breaking change:** offline worlds are no longer listed unless
+/// `includeOffline=true` is passed. To keep the previous behaviour, add
+/// `?includeOffline=true` and use `isOnline` to tell them apart.
 #[utoipa::path(
     get,
     operation_id = "get_worlds",
     path = "/api/v1/worlds",
+    params(QueryParams),
     responses(
-        (status = 200, description = "Success", body = WorldsResponse),
+        (status = 200, description = "Success", body = WorldsResponse, example = json!({
+            "playersOnlineTotal": 7015,
+            "recordPlayers": 64028,
+            "recordDate": "2007-11-28T18:26:00Z",
+            "worlds": [
+                {
+                    "name": "Antica",
+                    "tibiaUrl": "https://www.tibia.com/community/?subtopic=worlds&world=Antica",
+                    "playersOnlineCount": 438,
+                    "isOnline": true,
+                    "location": "europe",
+                    "pvpType": "open",
+                    "battlEye": true,
+                    "battlEyeType": "sinceDate",
+                    "battlEyeDate": "2017-08-29",
+                    "premiumRequired": false,
+                    "gameWorldType": "regular",
+                    "isTournamentWorld": false
+                },
+                {
+                    "name": "Zuna",
+                    "tibiaUrl": "https://www.tibia.com/community/?subtopic=worlds&world=Zuna",
+                    "playersOnlineCount": 10,
+                    "isOnline": true,
+                    "location": "europe",
+                    "pvpType": "hardcore",
+                    "battlEye": false,
+                    "battlEyeType": "unprotected",
+                    "premiumRequired": false,
+                    "transferType": "locked",
+                    "gameWorldType": "experimental",
+                    "isTournamentWorld": false
+                }
+            ]
+        })),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "Invalid value for parameter 'includeOffline': provided string was not true or false"
+        })),
         (status = 500, description = "Internal Server Error"),
         (status = 503, description = "Service Unavailable", body = PublicErrorBody)
     ),
@@ -29,19 +137,78 @@ use crate::{
 #[instrument(name = "Get Worlds", skip(state))]
 pub async fn get<S: Client>(
     State(state): State<AppState<S>>,
-) -> Result<Json<WorldsResponse>, ServerError> {
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<WorldsResponse>), ServerError> {
     let client = &state.client;
 
+    let (mut data_age, mut worlds) = get_worlds(client).await?;
+    state.remember_worlds(data_age, &worlds.worlds);
+
+    if !query_params.include_offline.unwrap_or(false) {
+        worlds.worlds.retain(|world| world.is_online);
+        worlds.players_online_total = worlds
+            .worlds
+            .iter()
+            .map(|world| world.players_online_count)
+            .sum();
+    }
+
+    if query_params.include_records.unwrap_or(false) {
+        // fetch up to 10 world pages in parallel (without preserving the order of the results)
+        let names = worlds
+            .worlds
+            .iter()
+            .map(|w| w.name.clone())
+            .collect::<Vec<_>>();
+        let stream = futures::stream::iter(names.into_iter().enumerate())
+            .map(|(index, name)| async move {
+                let world_details = get_world_details(client, &name).await;
+                (index, world_details)
+            })
+            .buffer_unordered(10);
+
+        for (index, world_details) in stream.collect::<Vec<_>>().await {
+            let (world_data_age, world_details) = world_details?;
+            data_age = data_age.oldest(world_data_age);
+            let world = &mut worlds.worlds[index];
+            world.players_online_record = Some(world_details.players_online_record);
+            world.players_online_record_date = Some(world_details.players_online_record_date);
+        }
+    }
+
+    let snapshot = WorldsSnapshot {
+        players_online_total: worlds.players_online_total,
+        record_players: worlds.record_players,
+    };
+    let previous = state.worlds_snapshot.lock().unwrap().replace(snapshot);
+    if query_params.include_delta.unwrap_or(false) {
+        worlds.delta_since_last_fetch = previous.map(|previous| WorldsDelta {
+            players_online_total: i64::from(snapshot.players_online_total)
+                - i64::from(previous.players_online_total),
+            record_players: i64::from(snapshot.record_players) - i64::from(previous.record_players),
+        });
+    }
+
+    if let Some(sort) = query_params.sort {
+        sort.sort(&mut worlds.worlds);
+    }
+
+    Ok((data_age, Json(worlds)))
+}
+
+#[instrument(skip(client))]
+pub async fn get_worlds<S: Client>(client: &S) -> Result<(DataAge, WorldsResponse), ServerError> {
     let response = client.fetch_worlds_page().await.map_err(|e| {
         tracing::error!("Failed to fetch worlds page: {:?}", e);
         e
     })?;
+    let data_age = DataAge::of(&response);
     let worlds = parse_worlds_page(response).await.map_err(|e| {
         tracing::error!("Failed to parse worlds page: {:?}", e);
         e
     })?;
 
-    Ok(Json(worlds))
+    Ok((data_age, worlds))
 }
 
 #[instrument(skip(response))]
@@ -49,39 +216,32 @@ async fn parse_worlds_page(response: Response) -> Result<WorldsResponse, ServerE
     let text = response.text().await?;
     let document = scraper::Html::parse_document(&text);
 
-    let title_selector = Selector::parse("title").expect("Invalid selector for title");
     let title = document
-        .select(&title_selector)
+        .select(&selectors::TITLE)
         .next()
         .and_then(|t| t.text().next())
         .unwrap_or_default();
 
-    println!("title: {}", title);
-
     if MAINTENANCE_TITLE == title {
         return Err(TibiaError::Maintenance)?;
     };
 
-    let selector = Selector::parse(".main-content").expect("Invalid selector for main content");
     let main_content = &document
-        .select(&selector)
+        .select(&selectors::MAIN_CONTENT)
         .next()
         .context("ElementRef for main content not found")?;
 
-    let tables_selector =
-        Selector::parse(".TableContent").expect("Invalid selector for worlds table");
-    let mut tables = main_content.select(&tables_selector);
+    let mut tables = main_content.select(&selectors::TABLE_CONTENT);
 
     let mut worlds_data = WorldsResponse {
         players_online_total: 0,
         record_players: 0,
         record_date: Utc::now(),
+        delta_since_last_fetch: None,
         worlds: vec![],
     };
 
     let record_table = tables.next().context("Record table not found")?;
-    tables.next(); // skip table
-    let worlds_table = tables.next().context("Worlds table not found")?;
 
     // RECORD PLAYERS
     let record_html = record_table.inner_html().sanitize();
@@ -113,10 +273,42 @@ async fn parse_worlds_page(response: Response) -> Result<WorldsResponse, ServerE
     worlds_data.record_players = record_players;
 
     // WORLDS
-    let world_row_relector =
-        Selector::parse("tr.Odd > td, tr.Even > td").expect("Invalid selector for world row");
-    let name_selector = Selector::parse("a").expect("Invalid selector for world name");
-    let mut cells = worlds_table.select(&world_row_relector);
+    // the worlds are listed in tables following a label, e.g. "Regular Worlds"
+    let mut is_tournament_table = false;
+    for table in tables {
+        let label = table
+            .select(&selectors::TABLE_CONTENT_HEADER_CELL)
+            .map(|cell| cell.text().collect::<String>())
+            .collect::<String>()
+            .sanitize();
+        if label.ends_with("Worlds") {
+            is_tournament_table = label.contains("Tournament");
+            continue;
+        }
+
+        worlds_data
+            .worlds
+            .extend(parse_worlds_table(table, is_tournament_table)?);
+    }
+    if worlds_data.worlds.is_empty() {
+        return Err(anyhow::anyhow!("Worlds table not found"))?;
+    }
+    worlds_data.players_online_total = worlds_data
+        .worlds
+        .iter()
+        .map(|world| world.players_online_count)
+        .sum();
+
+    Ok(worlds_data)
+}
+
+fn parse_worlds_table(
+    worlds_table: ElementRef,
+    is_tournament_table: bool,
+) -> Result<Vec<World>, ServerError> {
+    let battl_eye_date_re = Regex::new(r"since (.*)\.").unwrap();
+    let mut worlds = vec![];
+    let mut cells = worlds_table.select(&selectors::STRIPED_ROW_CELL);
     while let (
         Some(name),
         Some(players_online),
@@ -132,9 +324,10 @@ async fn parse_worlds_page(response: Response) -> Result<WorldsResponse, ServerE
         cells.next(),
         cells.next(),
     ) {
-        let battl_eye_selector =
-            Selector::parse(".HelperDivIndicator").expect("Invalid selector for battl eye");
-        let additional_information = additional_information.inner_html();
+        // only the text, the tooltips describe the other world types as well
+        let additional_information = additional_information.text().collect::<String>();
+        let is_tournament_world =
+            is_tournament_table || additional_information.contains("tournament");
 
         // TODO: split tags and parse with FromStr
         let game_world_type = if additional_information.contains("experimental") {
@@ -152,35 +345,28 @@ async fn parse_worlds_page(response: Response) -> Result<WorldsResponse, ServerE
             None
         };
 
-        // TODO: Simplify ?
         let battl_eye_attr = battl_eye
-            .select(&battl_eye_selector)
+            .select(&selectors::HELPER_DIV_INDICATOR)
             .next()
             .and_then(|e| e.value().attr("onmouseover"));
 
-        let battl_eye_date = battl_eye_attr.map_or_else(
-            || -> Result<Option<NaiveDate>> { Ok(None) },
-            |s| {
-                if s.contains("release") {
-                    return Ok(None);
-                }
-
-                let re = Regex::new(r"since (.*)\.").unwrap();
+        let (battl_eye_type, battl_eye_date) = match battl_eye_attr {
+            None => (BattlEyeType::Unprotected, None),
+            Some(s) if s.contains("release") => (BattlEyeType::SinceRelease, None),
+            Some(s) => {
+                let s = battl_eye_date_re
+                    .find(s)
+                    .context(format!("BattlEye date not found in {}", s))?
+                    .as_str();
+                let naive_date = NaiveDate::parse_from_str(s, "since %B %d, %Y.")
+                    .context(format!("Failed to parse date {}", s))?;
 
-                match re.find(s) {
-                    Some(mat) => {
-                        let s = mat.as_str();
-                        let naive_date = NaiveDate::parse_from_str(s, "since %B %d, %Y.")
-                            .context(format!("Failed to parse date {}", s))?;
-
-                        Ok(Some(naive_date))
-                    }
-                    None => Ok(None),
-                }
-            },
-        )?;
+                (BattlEyeType::SinceDate, Some(naive_date))
+            }
+        };
 
         let players_online = players_online.inner_html().sanitize().replace(',', "");
+        let is_online = players_online != "off";
         let players_online = match players_online.as_str() {
             "off" => 0,
             any => any.parse().context(format!(
@@ -188,25 +374,31 @@ async fn parse_worlds_page(response: Response) -> Result<WorldsResponse, ServerE
                 players_online
             ))?,
         };
+        let name = name
+            .select(&selectors::LINK)
+            .next()
+            .context("World name not found")?
+            .inner_html();
         let world = World {
-            name: name
-                .select(&name_selector)
-                .next()
-                .context("World name not found")?
-                .inner_html(),
+            tibia_url: tibia_url(&name),
+            name,
             players_online_count: players_online,
+            is_online,
             location: location.inner_html().parse()?,
             pvp_type: pvp_type.inner_html().parse().unwrap(),
-            battl_eye: !battl_eye.inner_html().is_empty(),
+            battl_eye: battl_eye_type != BattlEyeType::Unprotected,
+            battl_eye_type,
             battl_eye_date,
             premium_required,
             game_world_type,
             transfer_type,
+            is_tournament_world,
+            players_online_record: None,
+            players_online_record_date: None,
         };
 
-        worlds_data.players_online_total += world.players_online_count;
-        worlds_data.worlds.push(world);
+        worlds.push(world);
     }
 
-    Ok(worlds_data)
+    Ok(worlds)
 }
@@ -0,0 +1,156 @@
+use axum::{extract::State, Json};
+use capitalize::Capitalize;
+use chrono::Utc;
+use futures::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::worlds_world_name::get_world_details;
+use crate::{
+    models::{PvpType, WorldComparison, WorldDetails},
+    prelude::*,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+/// The minimum number of worlds that can be compared
+pub const MIN_COMPARED_WORLDS: usize = 2;
+/// The maximum number of worlds that can be compared
+pub const MAX_COMPARED_WORLDS: usize = 5;
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Comma separated names of the worlds to compare (2 to 5 worlds)
+    #[param(example = "Antica,Secura,Nostera")]
+    worlds: String,
+}
+
+impl QueryParams {
+    pub fn world_names(&self) -> Vec<String> {
+        self.worlds
+            .split(',')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(|name| name.capitalize())
+            .collect()
+    }
+}
+
+/// Compare Worlds
+///
+/// Compares the statistics of multiple worlds side by side.
+#[utoipa::path(
+    get,
+    operation_id = "get_worlds_compare",
+    path = "/api/v1/worlds/compare",
+    params(QueryParams),
+    responses(
+        (status = 200, description = "Success", body = [WorldComparison], example = json!([
+            {
+                "name": "Antica",
+                "tibiaUrl": "https://www.tibia.com/community/?subtopic=worlds&world=Antica",
+                "isOnline": true,
+                "playersOnlineCount": 455,
+                "playersOnlineRecord": 1055,
+                "playersOnlineRecordDate": "2020-05-01T15:58:30Z",
+                "creationDate": "1997-01-01",
+                "location": "europe",
+                "pvpType": "open",
+                "worldQuestTitles": ["Rise of Devovorga", "The Lightbearer", "Orcsoberfest"],
+                "battlEye": true,
+                "battlEyeDate": "2017-08-29",
+                "gameWorldType": "regular",
+                "premiumRequired": false,
+                "playersOnline": [
+                    { "name": "Urinchoklad", "level": 52, "vocation": "eliteKnight" }
+                ],
+                "playersOnlineRank": 1,
+                "pvpTypeCategory": "PvP",
+                "ageDays": 9686
+            }
+        ])),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "Between 2 and 5 worlds can be compared at once"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Compare Worlds", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<Vec<WorldComparison>>), ServerError> {
+    let client = &state.client;
+    let world_names = query_params.world_names();
+
+    if !(MIN_COMPARED_WORLDS..=MAX_COMPARED_WORLDS).contains(&world_names.len()) {
+        return Err(ServerError::InvalidParameter(format!(
+            "Between {MIN_COMPARED_WORLDS} and {MAX_COMPARED_WORLDS} worlds can be compared at once"
+        )));
+    }
+
+    // fetch up to 5 worlds in parallel (without preserving the order of the results)
+    let stream = futures::stream::iter(world_names.into_iter().enumerate())
+        .map(|(index, world_name)| async move {
+            let result = get_world_details(client, &world_name).await;
+            (index, result)
+        })
+        .buffer_unordered(5);
+
+    let mut results = stream.collect::<Vec<_>>().await;
+    // respond in the same order as the worlds were requested
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut data_age = DataAge::now();
+    let worlds = results
+        .into_iter()
+        .map(|(_, result)| {
+            let (world_data_age, world) = result?;
+            data_age = data_age.oldest(world_data_age);
+            Ok(world)
+        })
+        .collect::<Result<Vec<_>, ServerError>>()?;
+
+    Ok((data_age, Json(compare_worlds(worlds))))
+}
+
+fn compare_worlds(worlds: Vec<WorldDetails>) -> Vec<WorldComparison> {
+    let today = Utc::now().date_naive();
+    let players_online = worlds
+        .iter()
+        .map(|w| w.players_online_count)
+        .collect::<Vec<_>>();
+
+    worlds
+        .into_iter()
+        .map(|world| {
+            // worlds with the same amount of players online share the same rank
+            let players_online_rank = players_online
+                .iter()
+                .filter(|&&count| count > world.players_online_count)
+                .count() as u32
+                + 1;
+            let pvp_type_category = match world.pvp_type {
+                PvpType::Optional => "Non-PvP",
+                _ => "PvP",
+            }
+            .to_string();
+            let age_days = (today - world.creation_date).num_days().max(0) as u32;
+
+            WorldComparison {
+                world,
+                players_online_rank,
+                pvp_type_category,
+                age_days,
+            }
+        })
+        .collect()
+}
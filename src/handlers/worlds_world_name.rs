@@ -1,5 +1,10 @@
 use crate::models::{GameWorldType, Location, Player, PvpType, Vocation, WorldDetails};
-use crate::{prelude::*, AppState};
+use crate::{
+    prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query, parsing::parse_month},
+    AppState,
+};
 use anyhow::{anyhow, Context, Result};
 use axum::{
     extract::{Path, State},
@@ -11,8 +16,9 @@ use chrono_tz::Europe::Stockholm;
 use regex::Regex;
 use reqwest::Response;
 
-use scraper::Selector;
+use scraper::ElementRef;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use tracing::instrument;
 
 #[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
@@ -28,16 +34,59 @@ impl PathParams {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// The maximum number of online players to include
+    #[param(example = 10)]
+    players_limit: Option<usize>,
+    /// The number of online players to skip
+    #[param(example = 0)]
+    players_offset: Option<usize>,
+    /// Fetch the world from tibia.com, even if it was fetched recently (defaults to `false`)
+    #[serde(alias = "no_cache")]
+    #[param(example = false)]
+    no_cache: Option<bool>,
+}
+
+/// How long the details of a world are reused, the online players change constantly
+const WORLD_DETAILS_TTL: Duration = Duration::from_secs(60);
+
 /// World
 ///
+/// The online players can be limited with `playersLimit` and `playersOffset`, in which case
+/// `playersOnlineCount` is still the total number of players online.
 #[utoipa::path(
     get,
     operation_id = "get_world_details",
     path = "/api/v1/worlds/{world_name}",
-    params(PathParams),
+    params(PathParams, QueryParams),
     responses(
-        (status = 200, description = "Success", body = WorldDetails),
-        (status = 404, description = "Not Found"),
+        (status = 200, description = "Success", body = WorldDetails, example = json!({
+            "name": "Antica",
+            "tibiaUrl": "https://www.tibia.com/community/?subtopic=worlds&world=Antica",
+            "isOnline": true,
+            "playersOnlineCount": 455,
+            "playersOnlineRecord": 1055,
+            "playersOnlineRecordDate": "2020-05-01T15:58:30Z",
+            "creationDate": "1997-01-01",
+            "location": "europe",
+            "pvpType": "open",
+            "worldQuestTitles": ["Rise of Devovorga", "The Lightbearer", "Orcsoberfest"],
+            "battlEye": true,
+            "battlEyeDate": "2017-08-29",
+            "gameWorldType": "regular",
+            "premiumRequired": false,
+            "playersOnline": [
+                { "name": "Abbnor", "level": 32, "vocation": "knight" },
+                { "name": "Urinchoklad", "level": 52, "vocation": "eliteKnight" }
+            ]
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
         (status = 500, description = "Internal Server Error"),
         (status = 503, description = "Service Unavailable", body = PublicErrorBody)
     ),
@@ -47,25 +96,63 @@ impl PathParams {
 pub async fn get<S: Client>(
     State(state): State<AppState<S>>,
     Path(path_params): Path<PathParams>,
-) -> Result<Json<WorldDetails>, ServerError> {
-    let client = &state.client;
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<WorldDetails>), ServerError> {
     let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+    let (data_age, mut world_details) =
+        get_cached_world_details(&state, &world_name, query_params.no_cache.unwrap_or(false))
+            .await?;
+
+    world_details.players_online = world_details
+        .players_online
+        .into_iter()
+        .skip(query_params.players_offset.unwrap_or(0))
+        .take(query_params.players_limit.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok((data_age, Json(world_details)))
+}
+
+/// The details of a world, reused for [`WORLD_DETAILS_TTL`] unless `no_cache` is set,
+/// in which case they are fetched again and replace the cached ones
+pub async fn get_cached_world_details<S: Client>(
+    state: &AppState<S>,
+    world_name: &str,
+    no_cache: bool,
+) -> Result<(DataAge, WorldDetails), ServerError> {
+    state
+        .results
+        .get_or_fetch(
+            format!("world:{}", world_name.to_lowercase()),
+            WORLD_DETAILS_TTL,
+            no_cache,
+            get_world_details(&state.client, world_name),
+        )
+        .await
+}
 
+#[instrument(skip(client))]
+pub async fn get_world_details<S: Client>(
+    client: &S,
+    world_name: &str,
+) -> Result<(DataAge, WorldDetails), ServerError> {
     let response = client
-        .fetch_world_details_page(&world_name)
+        .fetch_world_details_page(world_name)
         .await
         .map_err(|e| {
             tracing::error!("Failed to fetch world page: {:?}", e);
             e
         })?;
-    let world_details = parse_world_details_page(response, &world_name)
+    let data_age = DataAge::of(&response);
+    let world_details = parse_world_details_page(response, world_name)
         .await
         .map_err(|e| {
             tracing::error!("Failed to parse world page: {:?}", e);
             e
         })?;
 
-    Ok(Json(world_details))
+    Ok((data_age, world_details))
 }
 
 #[instrument(skip(response))]
@@ -76,9 +163,8 @@ pub async fn parse_world_details_page(
     let text = response.text().await?;
     let document = scraper::Html::parse_document(&text);
 
-    let title_selector = Selector::parse("title").expect("Invalid selector for title");
     let title = document
-        .select(&title_selector)
+        .select(&selectors::TITLE)
         .next()
         .and_then(|t| t.text().next())
         .unwrap_or_default();
@@ -87,15 +173,12 @@ pub async fn parse_world_details_page(
         return Err(TibiaError::Maintenance)?;
     };
 
-    let selector = Selector::parse(".main-content").expect("Invalid selector for main content");
     let main_content = &document
-        .select(&selector)
+        .select(&selectors::MAIN_CONTENT)
         .next()
         .context("ElementRef for main content not found")?;
 
-    let tables_selector =
-        Selector::parse(".InnerTableContainer").expect("Invalid selector for worlds table");
-    let mut tables = main_content.select(&tables_selector);
+    let mut tables = main_content.select(&selectors::INNER_TABLE_CONTAINER);
 
     // is a 404 page
     if tables.clone().count() == 1 {
@@ -107,11 +190,11 @@ pub async fn parse_world_details_page(
     tables.next();
     let information_table = tables.next().context("Information table not found")?;
 
-    let cell_selector = Selector::parse("td").expect("Invalid selector for table cell");
-    let mut information_cells = information_table.select(&cell_selector);
+    let mut information_cells = information_table.select(&selectors::CELL);
 
     let mut world_details = WorldDetails {
         name: world_name.to_string(),
+        tibia_url: tibia_url(world_name),
         is_online: true,
         players_online_count: 0,
         players_online_record: 0,
@@ -128,6 +211,10 @@ pub async fn parse_world_details_page(
         players_online: vec![],
     };
 
+    let online_record_re = Regex::new(r"([\d,]+)").expect("Invalid regex");
+    let record_date_re = Regex::new(r"\(on (.*) CES?T\)").unwrap();
+    let battl_eye_date_re = Regex::new(r"since (.*)\.").expect("Invalid regex");
+
     while let (Some(header), Some(value)) = (information_cells.next(), information_cells.next()) {
         match header.inner_html().as_str() {
             "Status:" => {
@@ -148,9 +235,7 @@ pub async fn parse_world_details_page(
             }
             "Online Record:" => {
                 let record_html = value.inner_html().sanitize();
-                let re = Regex::new(r"([\d,]+)").expect("Invalid regex");
-
-                let online_record = re
+                let online_record = online_record_re
                     .find(&record_html)
                     .context("Online record not found")?
                     .as_str()
@@ -161,8 +246,7 @@ pub async fn parse_world_details_page(
                     .context(format!("Failed to parse online record {}", online_record))?;
                 world_details.players_online_record = online_record;
 
-                let re = Regex::new(r"\(on (.*) CES?T\)").unwrap();
-                let record_date = re
+                let record_date = record_date_re
                     .captures(&record_html)
                     .and_then(|c| c.get(1))
                     .context(format!("Record date not found in {}", record_html))?
@@ -179,12 +263,8 @@ pub async fn parse_world_details_page(
                 world_details.players_online_record_date = utc_time;
             }
             "Creation Date:" => {
-                let date_html = &value.inner_html().sanitize();
-                let date_html = format!("01 {date_html}");
-
-                let naive_date = NaiveDate::parse_from_str(&date_html, "%d %B %Y")
-                    .context(format!("Failed to parse creation date {}", &date_html))?;
-                world_details.creation_date = naive_date;
+                world_details.creation_date = parse_month(&value.inner_html().sanitize())
+                    .context("Failed to parse creation date")?;
             }
             "Location:" => {
                 world_details.location = value.inner_html().parse()?;
@@ -193,14 +273,7 @@ pub async fn parse_world_details_page(
                 world_details.pvp_type = value.inner_html().parse()?;
             }
             "World Quest Titles:" => {
-                let mut titles = vec![];
-                let title_selector = Selector::parse("a").expect("Invalid selector for titles");
-
-                for title in value.select(&title_selector) {
-                    titles.push(title.inner_html().sanitize());
-                }
-
-                world_details.world_quest_titles = titles;
+                world_details.world_quest_titles = parse_world_quest_titles(value);
             }
             "BattlEye Status:" => {
                 let string = value.inner_html();
@@ -209,8 +282,10 @@ pub async fn parse_world_details_page(
                 } else if string.contains("since") {
                     world_details.battl_eye = true;
 
-                    let re = Regex::new(r"since (.*)\.").expect("Invalid regex");
-                    let s = re.find(&string).context("Date not found")?.as_str();
+                    let s = battl_eye_date_re
+                        .find(&string)
+                        .context("Date not found")?
+                        .as_str();
                     let naive_date = NaiveDate::parse_from_str(s, "since %B %d, %Y.")
                         .context(format!("Failed to parse BattlEye date {}", s))?;
 
@@ -236,10 +311,13 @@ pub async fn parse_world_details_page(
                     value.inner_html().sanitize().to_lowercase().parse()?;
             }
             _ => {
+                if let Some(row) = header.parent().and_then(ElementRef::wrap) {
+                    log_unexpected_html("Unexpected world information row", &row);
+                }
                 return Err(anyhow!(format!(
                     "Unexpected header {:?}",
                     header.inner_html()
-                )))?
+                )))?;
             }
         }
     }
@@ -247,9 +325,7 @@ pub async fn parse_world_details_page(
     // Only try to parse players table if there are players online
     if world_details.players_online_count > 0 {
         let players_online_table = tables.next().context("Players online table not found")?;
-        let player_cell_selector =
-            Selector::parse("tr.Odd > td, tr.Even > td").expect("Invalid selector for player cell");
-        let mut player_cells = players_online_table.select(&player_cell_selector);
+        let mut player_cells = players_online_table.select(&selectors::STRIPED_ROW_CELL);
 
         while let (Some(name), Some(level), Some(vocation)) = (
             player_cells.next(),
@@ -279,3 +355,20 @@ pub async fn parse_world_details_page(
 
     Ok(world_details)
 }
+
+/// Parses the titles from the value cell of the `World Quest Titles:` row
+#[instrument(skip_all)]
+pub fn parse_world_quest_titles(value: ElementRef) -> Vec<String> {
+    value
+        .select(&selectors::LINK)
+        .map(|title| title.inner_html().sanitize())
+        .collect()
+}
+
+/// The url of a world on tibia.com
+pub fn tibia_url(world_name: &str) -> String {
+    format!(
+        "https://www.tibia.com/community/?subtopic=worlds&world={}",
+        urlencoding::encode(world_name)
+    )
+}
@@ -1,25 +1,87 @@
 use anyhow::{Context, Result};
 use axum::{
     extract::{Path, State},
+    http::HeaderName,
+    response::{IntoResponse, Response as AxumResponse},
     Json,
 };
+use futures::stream::StreamExt;
 use reqwest::Response;
-use scraper::Selector;
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
-use super::worlds_world_name::PathParams;
-use crate::{models::Guild, prelude::*, AppState};
+use super::{
+    guilds_guild_name::{get_guild, guild_url, tibia_url},
+    worlds_world_name::PathParams,
+};
+use crate::{
+    models::Guild, prelude::*, selectors, utils::data_age::DataAge, utils::extract::Query,
+    utils::parsing::absolute_url, utils::TOTAL_COUNT_HEADER, AppState,
+};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Look up whether each guild is at war and accepts applications.
+    /// This requires fetching every guild's page and is considerably slower,
+    /// so it also requires a `limit` of at most 50.
+    #[param(example = false)]
+    include_war_status: Option<bool>,
+    /// Only include guilds that are active (`true`) or still in formation (`false`)
+    #[param(example = true)]
+    active: Option<bool>,
+    /// The maximum number of guilds to include (at most 500)
+    #[param(example = 100)]
+    limit: Option<usize>,
+    /// The number of guilds to skip
+    #[param(example = 0)]
+    offset: Option<usize>,
+}
+
+/// The largest number of guilds that can be requested at once
+const MAX_LIMIT: usize = 500;
+/// The largest number of guilds that can be requested with their war status,
+/// as each of them takes a request to tibia.com
+const MAX_WAR_STATUS_LIMIT: usize = 50;
 
 /// Guilds
 ///
+/// The guilds can be limited with `limit` and `offset`, in which case the `X-Total-Count`
+/// header is still the total number of guilds.
 #[utoipa::path(
     get,
     operation_id = "get_world_guilds",
     path = "/api/v1/worlds/{world_name}/guilds",
-    params(PathParams),
+    params(PathParams, QueryParams),
     responses(
-        (status = 200, description = "Success", body = [Guild]),
-        (status = 404, description = "Not Found"),
+        (status = 200, description = "Success", body = [Guild], headers(
+            ("x-total-count" = usize, description = "The number of guilds, regardless of `limit` and `offset`")
+        ), example = json!([
+            {
+                "logo": "https://static.tibia.com/images/guildlogos/Black_Crown.gif",
+                "name": "Black Crown",
+                "guildUrl": "/api/v1/worlds/Jaguna/guilds/Black%20Crown",
+                "tibiaUrl": "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Black%20Crown",
+                "description": "GUILD HISPANA",
+                "active": true
+            },
+            {
+                "logo": "https://static.tibia.com/images/community/default_logo.gif",
+                "name": "Breaths",
+                "guildUrl": "/api/v1/worlds/Jaguna/guilds/Breaths",
+                "tibiaUrl": "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Breaths",
+                "active": false
+            }
+        ])),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "includeWarStatus requires a limit of at most 50"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
         (status = 500, description = "Internal Server Error"),
         (status = 503, description = "Service Unavailable", body = PublicErrorBody)
     ),
@@ -29,30 +91,108 @@ use crate::{models::Guild, prelude::*, AppState};
 pub async fn get<S: Client>(
     State(state): State<AppState<S>>,
     Path(path_params): Path<PathParams>,
-) -> Result<Json<Vec<Guild>>, ServerError> {
+    Query(query_params): Query<QueryParams>,
+) -> Result<AxumResponse, ServerError> {
     let client = &state.client;
     let world_name = path_params.world_name();
+    let limit = query_params.limit.unwrap_or(usize::MAX);
+    if query_params.limit.is_some_and(|limit| limit > MAX_LIMIT) {
+        return Err(ServerError::InvalidParameter(format!(
+            "limit must be at most {MAX_LIMIT}"
+        )));
+    }
+    let include_war_status = query_params.include_war_status.unwrap_or(false);
+    if include_war_status
+        && !matches!(query_params.limit, Some(limit) if limit <= MAX_WAR_STATUS_LIMIT)
+    {
+        return Err(ServerError::InvalidParameter(format!(
+            "includeWarStatus requires a limit of at most {MAX_WAR_STATUS_LIMIT}"
+        )));
+    }
+    state.ensure_world_exists(&world_name)?;
+
+    let (mut data_age, mut guilds) = get_guilds(client, &world_name).await?;
+
+    if let Some(active) = query_params.active {
+        guilds.retain(|guild| guild.active == active);
+    }
+
+    let total_count = guilds.len();
+    let mut guilds = guilds
+        .into_iter()
+        .skip(query_params.offset.unwrap_or(0))
+        .take(limit)
+        .collect::<Vec<_>>();
+
+    if include_war_status {
+        // fetch up to 10 guild pages in parallel (without preserving the order of the results)
+        let names = guilds.iter().map(|g| g.name.clone()).collect::<Vec<_>>();
+        let world_name = world_name.as_str();
+        let stream = futures::stream::iter(names.into_iter().enumerate())
+            .map(|(index, name)| async move {
+                let guild = get_guild(client, &name, Some(world_name)).await;
+                (index, guild)
+            })
+            .buffer_unordered(10);
 
-    let response = client.fetch_guilds_page(&world_name).await.map_err(|e| {
+        for (index, guild) in stream.collect::<Vec<_>>().await {
+            // leave the status of the guild unknown, rather than failing the whole list
+            let (guild_data_age, guild) = match guild {
+                Ok(guild) => guild,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to get war status of {}: {:?}",
+                        guilds[index].name,
+                        e
+                    );
+                    continue;
+                }
+            };
+            data_age = data_age.oldest(guild_data_age);
+            guilds[index].at_war = Some(!guild.wars.is_empty());
+            guilds[index].application_open = Some(guild.application_open);
+        }
+    }
+
+    Ok((
+        data_age,
+        [(
+            HeaderName::from_static(TOTAL_COUNT_HEADER),
+            total_count.to_string(),
+        )],
+        Json(guilds),
+    )
+        .into_response())
+}
+
+#[instrument(skip(client))]
+pub async fn get_guilds<S: Client>(
+    client: &S,
+    world_name: &str,
+) -> Result<(DataAge, Vec<Guild>), ServerError> {
+    let response = client.fetch_guilds_page(world_name).await.map_err(|e| {
         tracing::error!("Failed to fetch guilds page: {:?}", e);
         e
     })?;
-    let guilds = parse_guilds_page(response).await.map_err(|e| {
+    let data_age = DataAge::of(&response);
+    let guilds = parse_guilds_page(response, world_name).await.map_err(|e| {
         tracing::error!("Failed to parse guilds page: {:?}", e);
         e
     })?;
 
-    Ok(Json(guilds))
+    Ok((data_age, guilds))
 }
 
 #[instrument(skip(response))]
-async fn parse_guilds_page(response: Response) -> Result<Vec<Guild>, ServerError> {
+pub async fn parse_guilds_page(
+    response: Response,
+    world_name: &str,
+) -> Result<Vec<Guild>, ServerError> {
     let text = response.text().await?;
     let document = scraper::Html::parse_document(&text);
 
-    let title_selector = Selector::parse("title").expect("Invalid selector for title");
     let title = document
-        .select(&title_selector)
+        .select(&selectors::TITLE)
         .next()
         .and_then(|t| t.text().next())
         .unwrap_or_default();
@@ -61,15 +201,12 @@ async fn parse_guilds_page(response: Response) -> Result<Vec<Guild>, ServerError
         return Err(TibiaError::Maintenance)?;
     };
 
-    let selector = Selector::parse(".main-content").expect("Selector to be valid");
     let main_content = document
-        .select(&selector)
+        .select(&selectors::MAIN_CONTENT)
         .next()
         .context("ElementRef for main content not found")?;
 
-    let table_selector =
-        Selector::parse(".TableContainer table.TableContent").expect("Selector to be valid");
-    let mut tables = main_content.select(&table_selector);
+    let mut tables = main_content.select(&selectors::TABLE_CONTAINER_CONTENT);
 
     // assume 404
     if tables.clone().count() != 2 {
@@ -78,22 +215,19 @@ async fn parse_guilds_page(response: Response) -> Result<Vec<Guild>, ServerError
 
     let mut guilds = vec![];
 
-    let row_selector = Selector::parse("tr:not(:first-child)").expect("Invalid selector for rows");
-    let cell_selector = Selector::parse("td").expect("Invalid selector for cells");
-    let img_selector = Selector::parse("img").expect("Invalid selector for guild logo");
-
     for i in 0..2 {
         let table = tables.next().context("Guilds table not found")?;
 
-        let rows = table.select(&row_selector);
+        let rows = table.select(&selectors::ROW_WITHOUT_HEADER);
         for row in rows {
-            let mut cells = row.select(&cell_selector);
+            let mut cells = row.select(&selectors::CELL);
             let logo = cells
                 .next()
                 .context("Logo cell not found")?
-                .select(&img_selector)
+                .select(&selectors::IMG)
                 .next()
-                .and_then(|img| img.value().attr("src").map(|src| src.to_string()));
+                .and_then(|img| img.value().attr("src"))
+                .map(absolute_url);
 
             let mut name_description_iterator = cells
                 .next()
@@ -110,9 +244,13 @@ async fn parse_guilds_page(response: Response) -> Result<Vec<Guild>, ServerError
 
             guilds.push(Guild {
                 logo,
+                guild_url: guild_url(world_name, &name),
+                tibia_url: tibia_url(&name),
                 name,
                 description,
                 active: i == 0,
+                at_war: None,
+                application_open: None,
             });
         }
     }
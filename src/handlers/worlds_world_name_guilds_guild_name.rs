@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use capitalize::Capitalize;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::{
+    guilds_guild_name::{get_guild, QueryParams},
+    worlds_world_name_guilds::get_guilds,
+};
+use crate::{
+    models::PaginatedGuildDetails,
+    prelude::*,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+pub struct PathParams {
+    /// Name of world
+    #[param(example = "Antica")]
+    pub world_name: String,
+    /// Name of guild
+    #[param(example = "Red Rose")]
+    pub guild_name: String,
+}
+
+impl PathParams {
+    pub fn world_name(&self) -> String {
+        self.world_name.capitalize()
+    }
+}
+
+/// World Guild
+///
+/// The guild details like `/api/v1/guilds/{guild_name}`, for guilds of the given world only.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_guild",
+    path = "/api/v1/worlds/{world_name}/guilds/{guild_name}",
+    params(PathParams, QueryParams),
+    responses(
+        (status = 200, description = "Success", body = PaginatedGuildDetails, example = json!({
+            "name": "Red Rose",
+            "guildUrl": "/api/v1/worlds/Antica/guilds/Red%20Rose",
+            "tibiaUrl": "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Red%20Rose",
+            "logo": "https://static.tibia.com/images/guildlogos/Red_Rose.gif",
+            "description": "Hunting dragons together since 2019.",
+            "applicationOpen": true,
+            "createdAt": "2019-06-24",
+            "founder": "Urinchoklad",
+            "memberCount": 1,
+            "members": {
+                "data": [
+                    {
+                        "name": "Urinchoklad",
+                        "title": "Dragon Slayer",
+                        "rank": "Leader",
+                        "vocation": "eliteKnight",
+                        "level": 52,
+                        "joined": "2019-06-24",
+                        "isOnline": true
+                    }
+                ],
+                "page": 1,
+                "perPage": 50,
+                "totalItems": 1,
+                "totalPages": 1
+            },
+            "wars": []
+        })),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "perPage must be between 1 and 100"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get World Guild", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<PaginatedGuildDetails>), ServerError> {
+    let client = &state.client;
+    let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+    let (page, per_page) = query_params.pagination()?;
+
+    // the guild page doesn't tell the world, which the guilds of the world do
+    let ((guilds_data_age, guilds), (guild_data_age, guild)) = futures::try_join!(
+        get_guilds(client, &world_name),
+        get_guild(client, &path_params.guild_name, Some(&world_name))
+    )?;
+    if !guilds
+        .iter()
+        .any(|g| g.name.eq_ignore_ascii_case(&guild.name))
+    {
+        tracing::info!("Guild '{}' not found in {}", guild.name, world_name);
+        return Err(ServerError::not_found());
+    }
+
+    Ok((
+        guilds_data_age.oldest(guild_data_age),
+        Json(query_params.paginate(guild, page, per_page)),
+    ))
+}
@@ -0,0 +1,105 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::{
+    guilds_guild_name::get_guild, worlds_world_name_guilds::get_guilds,
+    worlds_world_name_guilds_guild_name::PathParams,
+};
+use crate::{
+    models::{GuildMember, OnlineStatus, Vocation},
+    prelude::*,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Only include members with this vocation (promoted or not)
+    vocation: Option<Vocation>,
+    /// Only include members with this rank
+    #[param(example = "Vice Leader")]
+    rank: Option<String>,
+    /// Only include members that are online or offline
+    status: Option<OnlineStatus>,
+    /// Only include members whose name contains this text, ignoring case
+    #[serde(alias = "name_contains")]
+    #[param(example = "bubble")]
+    name_contains: Option<String>,
+}
+
+/// Guild Members
+///
+/// The members of a guild of the given world, optionally filtered. Every filter has to match.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_guild_members",
+    path = "/api/v1/worlds/{world_name}/guilds/{guild_name}/members",
+    params(PathParams, QueryParams),
+    responses(
+        (status = 200, description = "Success", body = [GuildMember], example = json!([
+            {
+                "name": "Urinchoklad",
+                "title": "Dragon Slayer",
+                "rank": "Leader",
+                "vocation": "eliteKnight",
+                "level": 52,
+                "joined": "2019-06-24",
+                "isOnline": true
+            }
+        ])),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get Guild Members", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<Vec<GuildMember>>), ServerError> {
+    let client = &state.client;
+    let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+
+    // the guild page doesn't tell the world, which the guilds of the world do
+    let ((guilds_data_age, guilds), (guild_data_age, guild)) = futures::try_join!(
+        get_guilds(client, &world_name),
+        get_guild(client, &path_params.guild_name, Some(&world_name))
+    )?;
+    if !guilds
+        .iter()
+        .any(|g| g.name.eq_ignore_ascii_case(&guild.name))
+    {
+        tracing::info!("Guild '{}' not found in {}", guild.name, world_name);
+        return Err(ServerError::not_found());
+    }
+    let data_age = guilds_data_age.oldest(guild_data_age);
+    let mut members = guild.members;
+
+    if let Some(vocation) = query_params.vocation {
+        members.retain(|member| member.vocation.map(Vocation::base) == Some(vocation.base()));
+    }
+    if let Some(rank) = &query_params.rank {
+        members.retain(|member| &member.rank == rank);
+    }
+    if let Some(status) = query_params.status {
+        members.retain(|member| member.is_online == status.is_online());
+    }
+    if let Some(text) = &query_params.name_contains {
+        let text = text.to_lowercase();
+        members.retain(|member| member.name.to_lowercase().contains(&text));
+    }
+
+    Ok((data_age, Json(members)))
+}
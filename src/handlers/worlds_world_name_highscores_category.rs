@@ -0,0 +1,223 @@
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use capitalize::Capitalize;
+use reqwest::Response;
+use scraper::ElementRef;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    models::{HighscoreCategory, HighscoreEntry, PaginatedResponse, Vocation},
+    prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+/// The number of entries tibia.com lists per page
+pub const PER_PAGE: u32 = 25;
+/// The number of entries tibia.com lists in total
+pub const TOTAL_ITEMS: u32 = 300;
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+pub struct PathParams {
+    /// Name of world
+    #[param(example = "Antica")]
+    pub world_name: String,
+    /// The category to rank by, one of `experience`, `magicLevel`, `sword`, `axe`,
+    /// `club`, `distance`, `shield`, `fist`, `fishing`, `achievements` or `loyaltyPoints`
+    #[param(example = "experience")]
+    pub category: String,
+}
+
+impl PathParams {
+    pub fn world_name(&self) -> String {
+        self.world_name.capitalize()
+    }
+
+    pub fn category(&self) -> Result<HighscoreCategory, ServerError> {
+        self.category
+            .parse()
+            .map_err(|e: anyhow::Error| ServerError::InvalidParameter(e.to_string()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// The page to return, starting from 1 (at most 12)
+    #[param(example = 1)]
+    page: Option<u32>,
+    /// Only rank characters with this vocation (promoted or not)
+    vocation: Option<Vocation>,
+}
+
+/// Highscores
+///
+/// The highest ranked characters of a world, 25 per page.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_highscores",
+    path = "/api/v1/worlds/{world_name}/highscores/{category}",
+    params(PathParams, QueryParams),
+    responses(
+        (status = 200, description = "Success", body = PaginatedHighscores, example = json!({
+            "data": [
+                { "rank": 1, "name": "Urinchoklad", "vocation": "eliteKnight", "level": 52, "value": 2417300 }
+            ],
+            "page": 1,
+            "perPage": 25,
+            "totalItems": 300,
+            "totalPages": 12
+        })),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "page must be between 1 and 12"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get Highscores", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<PaginatedResponse<HighscoreEntry>>), ServerError> {
+    let client = &state.client;
+    let world_name = path_params.world_name();
+    let category = path_params.category()?;
+
+    let total_pages = TOTAL_ITEMS.div_ceil(PER_PAGE);
+    let page = query_params.page.unwrap_or(1);
+    if !(1..=total_pages).contains(&page) {
+        return Err(ServerError::InvalidParameter(format!(
+            "page must be between 1 and {total_pages}"
+        )));
+    }
+    state.ensure_world_exists(&world_name)?;
+
+    let response = client
+        .fetch_highscores_page(&world_name, &category, query_params.vocation, page)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch highscores page: {:?}", e);
+            e
+        })?;
+    let data_age = DataAge::of(&response);
+    let entries = parse_highscores_page(response).await.map_err(|e| {
+        tracing::error!("Failed to parse highscores page: {:?}", e);
+        e
+    })?;
+
+    // tibia.com already paginates the highscores
+    Ok((
+        data_age,
+        Json(PaginatedResponse {
+            data: entries,
+            page,
+            per_page: PER_PAGE,
+            total_items: TOTAL_ITEMS,
+            total_pages,
+        }),
+    ))
+}
+
+#[instrument(skip(response))]
+async fn parse_highscores_page(response: Response) -> Result<Vec<HighscoreEntry>, ServerError> {
+    let text = response.text().await?;
+    let document = scraper::Html::parse_document(&text);
+
+    let title = document
+        .select(&selectors::TITLE)
+        .next()
+        .and_then(|t| t.text().next())
+        .unwrap_or_default();
+
+    if MAINTENANCE_TITLE == title {
+        return Err(TibiaError::Maintenance)?;
+    };
+
+    let main_content = document
+        .select(&selectors::MAIN_CONTENT)
+        .next()
+        .context("ElementRef for main content not found")?;
+
+    let Some(highscores_table) = find_table(&main_content, "Highscores") else {
+        return Err(TibiaError::NotFound)?;
+    };
+
+    // the columns differ between categories, e.g. loyalty points include the title
+    let headers = highscores_table
+        .select(&selectors::TABLE_CONTENT_HEADER_CELL)
+        .map(|cell| cell.text().collect::<String>().sanitize())
+        .collect::<Vec<_>>();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .context(format!("Highscores column {name} not found"))
+    };
+    let vocation_column = column("Vocation")?;
+    let level_column = column("Level")?;
+
+    // the page navigation above and below the ranks shares the table
+    let mut entries = vec![];
+    for row in highscores_table.select(&selectors::STRIPED_ROW) {
+        let cells = row.select(&selectors::CELL).collect::<Vec<_>>();
+        if cells.len() != headers.len() {
+            log_unexpected_html("Unexpected highscores row", &row);
+            return Err(anyhow::anyhow!("Unexpected highscores row"))?;
+        }
+
+        entries.push(parse_highscores_row(&cells, vocation_column, level_column)?);
+    }
+
+    Ok(entries)
+}
+
+fn parse_highscores_row(
+    cells: &[ElementRef],
+    vocation_column: usize,
+    level_column: usize,
+) -> Result<HighscoreEntry, ServerError> {
+    let text = |cell: &ElementRef| cell.text().collect::<String>().sanitize();
+
+    let rank = text(&cells[0]);
+    let name = cells[1]
+        .select(&selectors::LINK)
+        .next()
+        .context("Highscores character link not found")?
+        .text()
+        .collect::<String>()
+        .sanitize();
+    let vocation = match text(&cells[vocation_column]).as_str() {
+        "None" => None,
+        v => Some(v.parse()?),
+    };
+    let level = text(&cells[level_column]);
+    // the points or skill level are always in the last column
+    let value = cells.last().map(text).unwrap_or_default().replace(',', "");
+
+    Ok(HighscoreEntry {
+        rank: rank
+            .parse()
+            .context(format!("Failed to parse highscores rank {}", rank))?,
+        name,
+        vocation,
+        level: level
+            .parse()
+            .context(format!("Failed to parse highscores level {}", level))?,
+        value: value
+            .parse()
+            .context(format!("Failed to parse highscores value {}", value))?,
+    })
+}
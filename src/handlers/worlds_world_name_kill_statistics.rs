@@ -4,26 +4,64 @@ use axum::{
     Json,
 };
 use reqwest::Response;
-use scraper::Selector;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use tracing::instrument;
 
 use super::worlds_world_name::PathParams;
 use crate::{
-    models::{KillStatistics, KilledAmounts, RaceKillStatistics},
+    models::{KillStatSort, KillStatistics, KilledAmounts, RaceKillStatistics},
     prelude::*,
+    selectors,
+    utils::{data_age::DataAge, extract::Query},
     AppState,
 };
 
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// The order of the races, by their statistics of the last day (defaults to `raceAsc`)
+    sort: Option<KillStatSort>,
+    /// Fetch the statistics from tibia.com, even if they were fetched recently (defaults to `false`)
+    #[serde(alias = "no_cache")]
+    #[param(example = false)]
+    no_cache: Option<bool>,
+}
+
+/// How long the statistics are reused, tibia.com only updates them at server save
+const KILL_STATISTICS_TTL: Duration = Duration::from_secs(60 * 60);
+
 /// Kill Statistics
 ///
 #[utoipa::path(
     get,
     operation_id = "get_world_kill_statistics",
     path = "/api/v1/worlds/{world_name}/kill-statistics",
-    params(PathParams),
+    params(PathParams, QueryParams),
     responses(
-        (status = 200, description = "Success", body = KillStatistics),
-        (status = 404, description = "Not Found"),
+        (status = 200, description = "Success", body = KillStatistics, example = json!({
+            "totalLastDay": { "killedPlayers": 850, "killedByPlayers": 1110296 },
+            "totalLastWeek": { "killedPlayers": 4830, "killedByPlayers": 8236238 },
+            "totalDeathsLastDay": 850,
+            "totalDeathsLastWeek": 4830,
+            "racesTotalLastDay": { "killedPlayers": 850, "killedByPlayers": 1110296 },
+            "racesTotalLastWeek": { "killedPlayers": 4830, "killedByPlayers": 8236238 },
+            "raceCount": 1250,
+            "races": [
+                {
+                    "race": "Abyssador",
+                    "lastDay": { "killedPlayers": 0, "killedByPlayers": 1 },
+                    "lastWeek": { "killedPlayers": 0, "killedByPlayers": 6 },
+                    "dailyAverageKilledPlayers": 0.0,
+                    "dailyAverageKilledByPlayers": 0.86
+                }
+            ]
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
         (status = 500, description = "Internal Server Error"),
         (status = 503, description = "Service Unavailable", body = PublicErrorBody)
     ),
@@ -33,22 +71,53 @@ use crate::{
 pub async fn get<S: Client>(
     State(state): State<AppState<S>>,
     Path(path_params): Path<PathParams>,
-) -> Result<Json<KillStatistics>, ServerError> {
-    let client = &state.client;
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<KillStatistics>), ServerError> {
     let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+
+    let (data_age, mut stats) =
+        get_kill_statistics(&state, &world_name, query_params.no_cache.unwrap_or(false)).await?;
+
+    if let Some(sort) = query_params.sort {
+        sort.sort(&mut stats.races);
+    }
+
+    Ok((data_age, Json(stats)))
+}
 
-    let response = client
-        .fetch_killstatistics_page(&world_name)
+/// The kill statistics of a world, reused for an hour unless `no_cache` is set
+pub async fn get_kill_statistics<S: Client>(
+    state: &AppState<S>,
+    world_name: &str,
+    no_cache: bool,
+) -> Result<(DataAge, KillStatistics), ServerError> {
+    let client = &state.client;
+
+    state
+        .results
+        .get_or_fetch(
+            format!("kill-statistics:{}", world_name.to_lowercase()),
+            KILL_STATISTICS_TTL,
+            no_cache,
+            async {
+                let response = client
+                    .fetch_killstatistics_page(world_name)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Failed to fetch kill statistics page: {:?}", e);
+                        e
+                    })?;
+                let data_age = DataAge::of(&response);
+                let stats = parse_killstatistics_page(response).await.map_err(|e| {
+                    tracing::error!("Failed to parse kill statistics page: {:?}", e);
+                    e
+                })?;
+
+                Ok((data_age, stats))
+            },
+        )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch kill statistics page: {:?}", e);
-            e
-        })?;
-    let guilds = parse_killstatistics_page(response).await.map_err(|e| {
-        tracing::error!("Failed to parse kill statistics page: {:?}", e);
-        e
-    })?;
-    Ok(Json(guilds))
 }
 
 #[instrument(skip(response))]
@@ -56,9 +125,8 @@ async fn parse_killstatistics_page(response: Response) -> Result<KillStatistics,
     let text = response.text().await?;
     let document = scraper::Html::parse_document(&text);
 
-    let title_selector = Selector::parse("title").expect("Invalid selector for title");
     let title = document
-        .select(&title_selector)
+        .select(&selectors::TITLE)
         .next()
         .and_then(|t| t.text().next())
         .unwrap_or_default();
@@ -67,16 +135,12 @@ async fn parse_killstatistics_page(response: Response) -> Result<KillStatistics,
         return Err(TibiaError::Maintenance)?;
     };
 
-    let selector = Selector::parse(".main-content").expect("Selector to be valid");
     let main_content = document
-        .select(&selector)
+        .select(&selectors::MAIN_CONTENT)
         .next()
         .context("ElementRef for main content not found")?;
 
-    let table_cell_selector = Selector::parse("#KillStatisticsTable tr.DataRow > td")
-        .expect("Invalid selector for kill statistics table");
-
-    let mut cells = main_content.select(&table_cell_selector);
+    let mut cells = main_content.select(&selectors::KILL_STATISTICS_CELL);
 
     // assume 404
     if cells.clone().count() == 0 {
@@ -85,14 +149,13 @@ async fn parse_killstatistics_page(response: Response) -> Result<KillStatistics,
 
     let mut stats: KillStatistics = KillStatistics {
         races: vec![],
-        total_last_day: KilledAmounts {
-            killed_players: 0,
-            killed_by_players: 0,
-        },
-        total_last_week: KilledAmounts {
-            killed_players: 0,
-            killed_by_players: 0,
-        },
+        total_last_day: KilledAmounts::default(),
+        total_last_week: KilledAmounts::default(),
+        total_deaths_last_day: 0,
+        total_deaths_last_week: 0,
+        races_total_last_day: KilledAmounts::default(),
+        races_total_last_week: KilledAmounts::default(),
+        race_count: 0,
     };
 
     while let (Some(name), Some(kp_day), Some(kbp_day), Some(kp_week), Some(kbp_week)) = (
@@ -134,12 +197,47 @@ async fn parse_killstatistics_page(response: Response) -> Result<KillStatistics,
                 .context("Failed to parse killed_by_players")?,
         };
 
-        stats.races.push(RaceKillStatistics {
-            race: name.to_string(),
+        stats.races.push(RaceKillStatistics::new(
+            name.to_string(),
             last_day,
             last_week,
-        })
+        ))
+    }
+
+    stats.total_deaths_last_day = stats.races.iter().map(|r| r.last_day.killed_players).sum();
+    stats.total_deaths_last_week = stats.races.iter().map(|r| r.last_week.killed_players).sum();
+    stats.races_total_last_day = stats.races.iter().map(|r| &r.last_day).sum();
+    stats.races_total_last_week = stats.races.iter().map(|r| &r.last_week).sum();
+    stats.race_count = stats.races.len() as u32;
+
+    for (period, parsed, computed) in [
+        (
+            "last day",
+            &stats.total_last_day,
+            &stats.races_total_last_day,
+        ),
+        (
+            "last week",
+            &stats.total_last_week,
+            &stats.races_total_last_week,
+        ),
+    ] {
+        if diverges(parsed, computed) {
+            tracing::warn!(
+                ?parsed,
+                ?computed,
+                "Kill statistics total for the {period} diverges from the sum of the races"
+            );
+        }
     }
 
     Ok(stats)
 }
+
+/// Whether the totals differ by more than 10%, which hints at rows that weren't parsed
+fn diverges(parsed: &KilledAmounts, computed: &KilledAmounts) -> bool {
+    let differs = |a: u32, b: u32| a.abs_diff(b) > a.max(b) / 10;
+
+    differs(parsed.killed_players, computed.killed_players)
+        || differs(parsed.killed_by_players, computed.killed_by_players)
+}
@@ -0,0 +1,96 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use capitalize::Capitalize;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::worlds_world_name_kill_statistics::get_kill_statistics;
+use crate::{
+    models::RaceKillStatisticsWithTotals,
+    prelude::*,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+pub struct PathParams {
+    /// Name of world
+    #[param(example = "Antica")]
+    pub world_name: String,
+    /// Name of the race as listed by tibia.com, ignoring case
+    #[param(example = "Dragons")]
+    pub race: String,
+}
+
+impl PathParams {
+    pub fn world_name(&self) -> String {
+        self.world_name.capitalize()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Fetch the statistics from tibia.com, even if they were fetched recently (defaults to `false`)
+    #[serde(alias = "no_cache")]
+    #[param(example = false)]
+    no_cache: Option<bool>,
+}
+
+/// Race Kill Statistics
+///
+/// The kill statistics of a single race, along with the totals of the world.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_race_kill_statistics",
+    path = "/api/v1/worlds/{world_name}/kill-statistics/{race}",
+    params(PathParams, QueryParams),
+    responses(
+        (status = 200, description = "Success", body = RaceKillStatisticsWithTotals, example = json!({
+            "race": "dragons",
+            "lastDay": { "killedPlayers": 3, "killedByPlayers": 8285 },
+            "lastWeek": { "killedPlayers": 34, "killedByPlayers": 61817 },
+            "dailyAverageKilledPlayers": 4.86,
+            "dailyAverageKilledByPlayers": 8831.0,
+            "totalLastDay": { "killedPlayers": 850, "killedByPlayers": 1110296 },
+            "totalLastWeek": { "killedPlayers": 4830, "killedByPlayers": 8236238 }
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get Race Kill Statistics", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<RaceKillStatisticsWithTotals>), ServerError> {
+    let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+
+    let (data_age, stats) =
+        get_kill_statistics(&state, &world_name, query_params.no_cache.unwrap_or(false)).await?;
+
+    let race = stats
+        .races
+        .into_iter()
+        .find(|race| race.race.eq_ignore_ascii_case(&path_params.race))
+        .ok_or_else(ServerError::not_found)?;
+
+    Ok((
+        data_age,
+        Json(RaceKillStatisticsWithTotals {
+            race,
+            total_last_day: stats.total_last_day,
+            total_last_week: stats.total_last_week,
+        }),
+    ))
+}
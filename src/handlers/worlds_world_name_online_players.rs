@@ -0,0 +1,141 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::worlds_world_name::{parse_world_details_page, PathParams};
+use crate::{
+    models::{PaginatedResponse, Player, Vocation},
+    prelude::*,
+    utils::{data_age::DataAge, extract::Query},
+    AppState,
+};
+
+/// The number of players per page unless requested otherwise
+pub const DEFAULT_PER_PAGE: u32 = 50;
+/// The maximum number of players that can be requested per page
+pub const MAX_PER_PAGE: u32 = 500;
+
+#[derive(Serialize, Deserialize, Debug, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct QueryParams {
+    /// Only include players with this vocation
+    vocation: Option<Vocation>,
+    /// Only include players of at least this level
+    #[param(example = 100)]
+    level_min: Option<u16>,
+    /// Only include players of at most this level
+    #[param(example = 200)]
+    level_max: Option<u16>,
+    /// The page to return, starting from 1
+    #[param(example = 1)]
+    page: Option<u32>,
+    /// The number of players per page (at most 500)
+    #[param(example = 50)]
+    per_page: Option<u32>,
+}
+
+/// Online Players
+///
+/// The players currently online on a world, without the rest of the world details.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_online_players",
+    path = "/api/v1/worlds/{world_name}/online-players",
+    params(PathParams, QueryParams),
+    responses(
+        (status = 200, description = "Success", body = PaginatedPlayers, example = json!({
+            "data": [
+                { "name": "Abendroth", "level": 54, "vocation": "eliteKnight" },
+                { "name": "Urinchoklad", "level": 52, "vocation": "eliteKnight" }
+            ],
+            "page": 1,
+            "perPage": 50,
+            "totalItems": 2,
+            "totalPages": 1
+        })),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "page must be at least 1"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get Online Players", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+    Query(query_params): Query<QueryParams>,
+) -> Result<(DataAge, Json<PaginatedResponse<Player>>), ServerError> {
+    let client = &state.client;
+    let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+
+    let page = query_params.page.unwrap_or(1);
+    if page < 1 {
+        return Err(ServerError::InvalidParameter(
+            "page must be at least 1".to_string(),
+        ));
+    }
+    let per_page = query_params.per_page.unwrap_or(DEFAULT_PER_PAGE);
+    if !(1..=MAX_PER_PAGE).contains(&per_page) {
+        return Err(ServerError::InvalidParameter(format!(
+            "perPage must be between 1 and {MAX_PER_PAGE}"
+        )));
+    }
+
+    let response = client
+        .fetch_world_details_page(&world_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch world page: {:?}", e);
+            e
+        })?;
+    let data_age = DataAge::of(&response);
+    let world_details = parse_world_details_page(response, &world_name)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to parse world page: {:?}", e);
+            e
+        })?;
+
+    let players = world_details
+        .players_online
+        .into_iter()
+        .filter(|player| matches_filters(player, &query_params))
+        .collect();
+
+    Ok((
+        data_age,
+        Json(PaginatedResponse::paginate(players, page, per_page)),
+    ))
+}
+
+fn matches_filters(player: &Player, query_params: &QueryParams) -> bool {
+    if let Some(vocation) = query_params.vocation {
+        if player.vocation != Some(vocation) {
+            return false;
+        }
+    }
+    if let Some(level_min) = query_params.level_min {
+        if player.level < level_min.into() {
+            return false;
+        }
+    }
+    if let Some(level_max) = query_params.level_max {
+        if player.level > level_max.into() {
+            return false;
+        }
+    }
+
+    true
+}
@@ -0,0 +1,48 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use tracing::instrument;
+
+use super::worlds_world_name::{get_world_details, PathParams};
+use crate::{models::OnlineRecord, prelude::*, utils::data_age::DataAge, AppState};
+
+/// Online Players Record
+///
+/// The record number of players online on a world, without the rest of the world details.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_players_online_record",
+    path = "/api/v1/worlds/{world_name}/players-online/record",
+    params(PathParams),
+    responses(
+        (status = 200, description = "Success", body = OnlineRecord, example = json!({
+            "count": 1055,
+            "date": "2020-05-01T15:58:30Z"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get World Online Players Record", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+) -> Result<(DataAge, Json<OnlineRecord>), ServerError> {
+    let client = &state.client;
+    let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+    let (data_age, world_details) = get_world_details(client, &world_name).await?;
+
+    let record = OnlineRecord {
+        count: world_details.players_online_record,
+        date: world_details.players_online_record_date,
+    };
+
+    Ok((data_age, Json(record)))
+}
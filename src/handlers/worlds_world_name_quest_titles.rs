@@ -0,0 +1,44 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use tracing::instrument;
+
+use super::worlds_world_name::{get_world_details, PathParams};
+use crate::{prelude::*, utils::data_age::DataAge, AppState};
+
+/// World Quest Titles
+///
+/// The world quest titles achieved on a world, without the rest of the world details.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_quest_titles",
+    path = "/api/v1/worlds/{world_name}/quest-titles",
+    params(PathParams),
+    responses(
+        (status = 200, description = "Success", body = [String], example = json!([
+            "Rise of Devovorga",
+            "The Lightbearer",
+            "Orcsoberfest"
+        ])),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get World Quest Titles", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+) -> Result<(DataAge, Json<Vec<String>>), ServerError> {
+    let client = &state.client;
+    let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+    let (data_age, world_details) = get_world_details(client, &world_name).await?;
+
+    Ok((data_age, Json(world_details.world_quest_titles)))
+}
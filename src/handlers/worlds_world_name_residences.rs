@@ -1,22 +1,28 @@
 use anyhow::{Context, Result};
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Path, State},
+    response::{IntoResponse, Response as AxumResponse},
     Json,
 };
-use chrono::{Duration, Timelike};
 use itertools::Itertools;
-use regex::Regex;
 use reqwest::Response;
 
 use futures::stream::StreamExt;
-use scraper::Selector;
+use scraper::ElementRef;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
 use super::worlds_world_name::PathParams;
 use crate::{
-    models::{Residence, ResidenceStatus, ResidenceType},
+    models::{Gold, Residence, ResidenceCsvRecord, ResidenceStatus, ResidenceType, ResponseFormat},
     prelude::*,
+    selectors,
+    utils::{
+        csv::csv_response,
+        data_age::DataAge,
+        extract::Query,
+        parsing::{parse_gold, parse_time_left, to_title_case},
+    },
     AppState,
 };
 
@@ -26,19 +32,26 @@ pub struct QueryParams {
     /// The town for which to fetch residences
     #[param(example = "Thais")]
     town: Option<String>,
-    /// Filter residences by type
+    /// Filter residences by type (`house` or `guildhall`)
     #[serde(rename = "type")]
     residence_type: Option<ResidenceType>,
+    /// The format of the response, `csv` downloads the residences as a file
+    format: Option<ResponseFormat>,
 }
 
 impl QueryParams {
+    /// The town as tibia.com spells it, regardless of the requested casing
     pub fn town(&self) -> Option<String> {
-        self.town.clone()
+        self.town.as_deref().map(to_title_case)
     }
 
     pub fn residence_type(&self) -> Option<ResidenceType> {
         self.residence_type
     }
+
+    pub fn format(&self) -> ResponseFormat {
+        self.format.unwrap_or_default()
+    }
 }
 
 /// Residences
@@ -49,8 +62,53 @@ impl QueryParams {
     path = "/api/v1/worlds/{world_name}/residences",
     params(PathParams, QueryParams),
     responses(
-        (status = 200, description = "Success", body = [Residence]),
-        (status = 404, description = "Not Found"),
+        (status = 200, description = "Success", content(
+            ("application/json" = [Residence], example = json!([
+            {
+                "id": 50101,
+                "world": "Jaguna",
+                "town": "Edron",
+                "type": "house",
+                "name": "Castle Shop 1",
+                "size": 42,
+                "rent": 400000,
+                "status": { "type": "rented" }
+            },
+            {
+                "id": 50102,
+                "world": "Jaguna",
+                "town": "Edron",
+                "type": "house",
+                "name": "Castle Shop 2",
+                "size": 42,
+                "rent": 400000,
+                "status": {
+                    "type": "auctionWithBid",
+                    "bid": 1555555,
+                    "expiryTime": "2023-09-18T07:00:00Z"
+                }
+            },
+            {
+                "id": 50201,
+                "world": "Jaguna",
+                "town": "Edron",
+                "type": "house",
+                "name": "Castle Street 1",
+                "size": 60,
+                "rent": 300000,
+                "status": { "type": "auctionNoBid" }
+            }
+        ])),
+            ("text/csv" = String, example = json!("id,world,town,type,name,size,rent,status,bid,expiryTime\n50101,Jaguna,Edron,house,Castle Shop 1,42,400000,rented,,\n"))
+        )),
+        (status = 400, description = "Bad Request", body = ClientError, example = json!({
+            "code": "INVALID_PARAMETER",
+            "message": "Invalid value for parameter 'type': expected one of: house, guildhall"
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
         (status = 500, description = "Internal Server Error"),
         (status = 503, description = "Service Unavailable", body = PublicErrorBody)
     ),
@@ -61,9 +119,10 @@ pub async fn get<S: Client>(
     State(state): State<AppState<S>>,
     Path(path_params): Path<PathParams>,
     Query(query_params): Query<QueryParams>,
-) -> Result<Json<Vec<Residence>>, ServerError> {
+) -> Result<AxumResponse, ServerError> {
     let client = &state.client;
     let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
     let towns = match query_params.town() {
         Some(t) => vec![t],
         None => {
@@ -76,40 +135,62 @@ pub async fn get<S: Client>(
         .map(|t| vec![t])
         .unwrap_or(vec![ResidenceType::House, ResidenceType::Guildhall]);
 
+    let (data_age, residences) = get_residences(client, &world_name, &towns, &residence_types)
+        .await
+        .map_err(|e| {
+            tracing::error!("Could not get residences for {world_name}: {:?}", e);
+            e
+        })?;
+
+    match query_params.format() {
+        ResponseFormat::Json => Ok((data_age, Json(residences)).into_response()),
+        ResponseFormat::Csv => {
+            let csv = csv_response(
+                ResidenceCsvRecord::HEADERS,
+                residences.iter().map(ResidenceCsvRecord::from),
+                "residences.csv",
+            )?;
+            Ok((data_age, csv).into_response())
+        }
+    }
+}
+
+/// Fetches the residences of every combination of town and residence type
+#[instrument(skip(client))]
+pub async fn get_residences<S: Client>(
+    client: &S,
+    world_name: &str,
+    towns: &[String],
+    residence_types: &[ResidenceType],
+) -> Result<(DataAge, Vec<Residence>), ServerError> {
     let mut combinations = Vec::with_capacity(towns.len() * residence_types.len());
-    for town in &towns {
-        for residence_type in &residence_types {
+    for town in towns {
+        for residence_type in residence_types {
             combinations.push((*residence_type, town.to_string()))
         }
     }
 
     // create an iterator of futures to execute
-    let futures =
-        (0..combinations.len()).map(|n| {
-            let combination = combinations.get(n).unwrap().clone();
-            let world_name = world_name.clone();
-            async move {
-                get_world_residences(client, &world_name, &combination.0, &combination.1).await
-            }
+    let futures = combinations
+        .into_iter()
+        .map(|(residence_type, town)| async move {
+            get_world_residences(client, world_name, &residence_type, &town).await
         });
 
     // create a buffered stream that will execute up to 10 futures in parallel
     // (without preserving the order of the results)
     let stream = futures::stream::iter(futures).buffer_unordered(10);
 
-    // wait for all futures to complete
-    let results = stream.collect::<Vec<_>>().await;
-
-    let residences = results
-        .into_iter()
-        .flatten_ok()
-        .collect::<Result<Vec<Residence>, ServerError>>()
-        .map_err(|e| {
-            tracing::error!("Could not get residences: {:?}", e);
-            e
-        })?;
+    // wait for all futures to complete, the data is as old as the oldest page
+    let mut data_age = DataAge::now();
+    let mut residences = Vec::new();
+    for result in stream.collect::<Vec<_>>().await {
+        let (page_data_age, page_residences) = result?;
+        data_age = data_age.oldest(page_data_age);
+        residences.extend(page_residences);
+    }
 
-    Ok(Json(residences))
+    Ok((data_age, residences))
 }
 
 #[instrument(skip(client))]
@@ -118,18 +199,19 @@ pub async fn get_world_residences<S: Client>(
     world_name: &str,
     residence_type: &ResidenceType,
     town: &str,
-) -> Result<Vec<Residence>, ServerError> {
+) -> Result<(DataAge, Vec<Residence>), ServerError> {
     let response = client
         .fetch_residences_page(world_name, residence_type, town)
         .await
         .map_err(|e| {
             tracing::error!(
-                "Failed to residences for {world_name}, {:?}, {town}: {:?}",
+                "Failed to fetch residences page for {world_name}, {:?}, {town}: {:?}",
                 residence_type,
                 e
             );
             e
         })?;
+    let data_age = DataAge::of(&response);
     let houses = parse_residences_page(response, world_name, residence_type, town)
         .await
         .map_err(|e| {
@@ -141,7 +223,7 @@ pub async fn get_world_residences<S: Client>(
             e
         })?;
 
-    Ok(houses)
+    Ok((data_age, houses))
 }
 
 #[instrument(skip(response))]
@@ -154,9 +236,8 @@ async fn parse_residences_page(
     let text = response.text().await?;
     let document = scraper::Html::parse_document(&text);
 
-    let title_selector = Selector::parse("title").expect("Invalid selector for title");
     let title = document
-        .select(&title_selector)
+        .select(&selectors::TITLE)
         .next()
         .and_then(|t| t.text().next())
         .unwrap_or_default();
@@ -165,15 +246,13 @@ async fn parse_residences_page(
         return Err(TibiaError::Maintenance)?;
     };
 
-    let selector = Selector::parse(".main-content").expect("Selector to be valid");
     let main_content = document
-        .select(&selector)
+        .select(&selectors::MAIN_CONTENT)
         .next()
         .context("ElementRef for main content not found")?;
 
-    let header_selector = Selector::parse(".Text").expect("Selector to be invalid");
     let title = main_content
-        .select(&header_selector)
+        .select(&selectors::TEXT)
         .next()
         .context("ElementRef for title not found")?;
     let title = title.text().next().context("Could not get title text")?;
@@ -185,24 +264,23 @@ async fn parse_residences_page(
     if re.find(title).is_none() {
         return Err(TibiaError::NotFound)?;
     }
-    let table_selector =
-        Selector::parse(".TableContainer table.TableContent").expect("Selector to be valid");
-    let mut tables = main_content.select(&table_selector);
-
-    // assume 404
-    if tables.clone().count() != 3 {
-        return Err(TibiaError::NotFound)?;
+    let mut tables = main_content.select(&selectors::TABLE_CONTAINER_CONTENT);
+
+    // the title matched, so the world and town exist and the markup has changed
+    let table_count = tables.clone().count();
+    if table_count != 3 {
+        log_unexpected_html("Unexpected residences tables", &main_content);
+        return Err(ServerError::ScrapeUnexpectedPageContent(format!(
+            "Expected 3 residences tables, found {table_count}"
+        )));
     }
 
-    let row_selector = Selector::parse("tr").expect("Selector to be valid");
-    let house_rows = tables.next().unwrap().select(&row_selector).skip(1);
+    let house_rows = tables.next().unwrap().select(&selectors::ROW).skip(1);
 
-    let towns_selector =
-        Selector::parse("input[name=town]").expect("Invalid selector for towns row");
     let towns = tables
         .last()
         .unwrap()
-        .select(&towns_selector)
+        .select(&selectors::TOWN_INPUT)
         .map(|e| e.value().attr("value"))
         .collect::<Option<Vec<_>>>()
         .context("Failed to parse towns")?;
@@ -215,125 +293,88 @@ async fn parse_residences_page(
 
     let mut residences = vec![];
 
-    let house_id_selector = Selector::parse("input[name=\"houseid\"]").expect("Invalid selector");
-
     let column_count = house_rows.clone().next().map(|r| r.text().count());
     if let Some(1) = column_count {
         return Ok(vec![]);
     }
 
+    let number_re = regex::Regex::new(r"(\d+)").unwrap();
+
     for row in house_rows {
-        let house_id = row
-            .select(&house_id_selector)
-            .next()
-            .context("House id input not found")?;
-        let house_id = house_id
-            .value()
-            .attr("value")
-            .and_then(|s| s.parse::<u32>().ok())
-            .context("Could not parse house id {house_id}")?;
-
-        let (name, size, rent, status) = row
-            .text()
-            .collect_tuple()
-            .context("Residence row does not contain 4 columns")?;
-
-        let number_re = regex::Regex::new(r"(\d+)").unwrap();
-        let size = number_re
-            .captures(size)
-            .and_then(|s| s.get(1))
-            .and_then(|s| s.as_str().parse().ok())
-            .context(format!("Failed to parse size: {}", size))?;
-
-        let rent = number_re
-            .captures(rent)
-            .and_then(|s| s.get(1))
-            .and_then(|s| s.as_str().parse::<u32>().ok())
-            .map(|s| s * 1000)
-            .context(format!("Failed to parse rent: {}", rent))?;
-
-        let value = status.to_string().sanitize();
-        let status = match value.as_str() {
-            "rented" => ResidenceStatus::Rented,
-            "auctioned (no bid yet)" => ResidenceStatus::AuctionNoBid,
-            _ => {
-                let gold_re = Regex::new(r"(\d+) gold").expect("Invalid residence gold regex");
-                let gold_str = gold_re
-                    .captures(&value)
-                    .and_then(|m| m.get(1))
-                    .map(|g| g.as_str())
-                    .context(format!("Expected gold in residence status: `{}`", value))?;
-                let gold = gold_str
-                    .parse::<u32>()
-                    .context(format!("Failed to parse gold `{:?}`", gold_str))?;
-
-                if value.contains("finished") {
-                    ResidenceStatus::AuctionFinished { bid: gold }
-                } else {
-                    let time_re = Regex::new(r"(\d+) (days?|hours?) left")
-                        .expect("Invalid residence time regex");
-                    let time_matches = time_re
-                        .captures(&value)
-                        .context(format!("Time not found: `{}`", value))?;
-
-                    let time: i64 = time_matches
-                        .get(1)
-                        .map(|t| t.as_str())
-                        .and_then(|t| t.parse().ok())
-                        .context("Could not parse time")?;
-                    let time_unit = time_matches
-                        .get(2)
-                        .map(|u| u.as_str())
-                        .context("Could not parse time unit")?;
-
-                    let current_dt = chrono::Utc::now()
-                        .with_minute(0)
-                        .and_then(|d| d.with_second(0))
-                        .and_then(|d| d.with_nanosecond(0))
-                        .context("Failed to construct current time")?;
-
-                    let current_hour = current_dt.hour();
-                    // if unit is days, set hour to 8 (utc server save)
-                    // otherwise we need to add an hour (0h30min left => set min 0 and add hour)
-                    let current_dt = match time_unit {
-                        "day" | "days" => {
-                            current_dt.with_hour(8).context("Failed to set hour to 8")?
-                        }
-                        _ => current_dt
-                            .with_hour(current_hour + 1)
-                            .context("Failed to add hour")?,
-                    };
-
-                    let duration = match time_unit {
-                        "day" | "days" => Duration::days(time),
-                        "hour" | "hours" => Duration::hours(time),
-                        // Because of the regex this cannot happen
-                        _ => panic!("Invalid time unit"),
-                    };
-
-                    let expires_dt = current_dt.checked_add_signed(duration).context(format!(
-                        "Failed to calculate expiry time `{time}` with unit `{time_unit}`"
-                    ))?;
-                    ResidenceStatus::AuctionWithBid {
-                        bid: gold,
-                        expiry_time: expires_dt,
-                    }
-                }
-            }
-        };
-
-        let residence = Residence {
-            id: house_id,
-            residence_type: *residence_type,
-            name: name.to_string().sanitize(),
-            size,
-            rent,
-            status,
-            town: town.to_string(),
-        };
+        let residence = parse_residence_row(row, world_name, residence_type, town, &number_re)
+            .inspect_err(|_| {
+                log_unexpected_html("Failed to parse residence row", &row);
+            })?;
 
         residences.push(residence)
     }
 
     Ok(residences)
 }
+
+fn parse_residence_row(
+    row: ElementRef,
+    world_name: &str,
+    residence_type: &ResidenceType,
+    town: &str,
+    number_re: &regex::Regex,
+) -> Result<Residence, ServerError> {
+    let house_id = row
+        .select(&selectors::HOUSE_ID_INPUT)
+        .next()
+        .context("House id input not found")?;
+    let house_id = house_id
+        .value()
+        .attr("value")
+        .and_then(|s| s.parse::<u32>().ok())
+        .context("Could not parse house id {house_id}")?;
+
+    let (name, size, rent, status) = row
+        .text()
+        .collect_tuple()
+        .context("Residence row does not contain 4 columns")?;
+
+    let size = number_re
+        .captures(size)
+        .and_then(|s| s.get(1))
+        .and_then(|s| s.as_str().parse().ok())
+        .context(format!("Failed to parse size: {}", size))?;
+
+    let rent = number_re
+        .captures(rent)
+        .and_then(|s| s.get(1))
+        .and_then(|s| s.as_str().parse::<u32>().ok())
+        .map(|s| Gold(s * 1000))
+        .context(format!("Failed to parse rent: {}", rent))?;
+
+    let value = status.to_string().sanitize();
+    let status = match value.as_str() {
+        "rented" => ResidenceStatus::Rented,
+        "auctioned (no bid yet)" => ResidenceStatus::AuctionNoBid,
+        _ => {
+            let gold = Gold(parse_gold(&value)?);
+
+            if value.contains("finished") {
+                ResidenceStatus::AuctionFinished { bid: gold }
+            } else {
+                ResidenceStatus::AuctionWithBid {
+                    bid: gold,
+                    expiry_time: parse_time_left(&value, chrono::Utc::now())?,
+                }
+            }
+        }
+    };
+
+    let residence = Residence {
+        id: house_id,
+        world: world_name.to_string(),
+        residence_type: *residence_type,
+        name: name.to_string().sanitize(),
+        size,
+        rent,
+        status,
+        town: town.to_string(),
+    };
+
+    Ok(residence)
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use tracing::instrument;
+
+use super::{
+    worlds_world_name::{get_world_details, PathParams},
+    worlds_world_name_guilds::parse_guilds_page,
+    worlds_world_name_residences::get_residences,
+};
+use crate::{
+    models::{ResidenceStatus, ResidenceType, WorldSummary},
+    prelude::*,
+    utils::data_age::DataAge,
+    AppState,
+};
+
+/// World Summary
+///
+/// The world details together with counts of its guilds, players online by vocation and
+/// houses up for auction. Counts that could not be fetched are `null`.
+#[utoipa::path(
+    get,
+    operation_id = "get_world_summary",
+    path = "/api/v1/worlds/{world_name}/summary",
+    params(PathParams),
+    responses(
+        (status = 200, description = "Success", body = WorldSummary, example = json!({
+            "name": "Antica",
+            "tibiaUrl": "https://www.tibia.com/community/?subtopic=worlds&world=Antica",
+            "isOnline": true,
+            "playersOnlineCount": 455,
+            "playersOnlineRecord": 1055,
+            "playersOnlineRecordDate": "2020-05-01T15:58:30Z",
+            "creationDate": "1997-01-01",
+            "location": "europe",
+            "pvpType": "open",
+            "worldQuestTitles": ["Rise of Devovorga", "The Lightbearer", "Orcsoberfest"],
+            "battlEye": true,
+            "battlEyeDate": "2017-08-29",
+            "gameWorldType": "regular",
+            "premiumRequired": false,
+            "playersOnline": [
+                { "name": "Urinchoklad", "level": 52, "vocation": "eliteKnight" }
+            ],
+            "guildCount": null,
+            "playersOnlineByVocation": { "eliteKnight": 1 },
+            "activeHouseAuctions": 104
+        })),
+        (status = 404, description = "Not Found", body = ClientError, example = json!({
+            "code": "RESOURCE_NOT_FOUND",
+            "message": "The requested resource was not found."
+        })),
+        (status = 500, description = "Internal Server Error"),
+        (status = 503, description = "Service Unavailable", body = PublicErrorBody)
+    ),
+    tag = "Worlds"
+)]
+#[instrument(name = "Get World Summary", skip(state))]
+pub async fn get<S: Client>(
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+) -> Result<(DataAge, Json<WorldSummary>), ServerError> {
+    let client = &state.client;
+    let world_name = path_params.world_name();
+    state.ensure_world_exists(&world_name)?;
+    let towns = state.towns.lock().unwrap().clone();
+
+    let (world_details, guild_count, active_house_auctions) = tokio::join!(
+        get_world_details(client, &world_name),
+        get_guild_count(client, &world_name),
+        get_active_house_auctions(client, &world_name, &towns),
+    );
+    // the summary is pointless without the world itself
+    let (mut data_age, world) = world_details?;
+    let guild_count = guild_count.ok().map(|(guilds_data_age, count)| {
+        data_age = data_age.oldest(guilds_data_age);
+        count
+    });
+    let active_house_auctions = active_house_auctions.ok().map(|(houses_data_age, count)| {
+        data_age = data_age.oldest(houses_data_age);
+        count
+    });
+
+    let mut players_online_by_vocation = BTreeMap::new();
+    for player in &world.players_online {
+        let vocation = player.vocation.map(|v| v.as_str()).unwrap_or("none");
+        *players_online_by_vocation
+            .entry(vocation.to_string())
+            .or_insert(0) += 1;
+    }
+
+    let summary = WorldSummary {
+        world,
+        guild_count,
+        players_online_by_vocation,
+        active_house_auctions,
+    };
+
+    Ok((data_age, Json(summary)))
+}
+
+#[instrument(skip(client))]
+async fn get_guild_count<S: Client>(
+    client: &S,
+    world_name: &str,
+) -> Result<(DataAge, u32), ServerError> {
+    let response = client.fetch_guilds_page(world_name).await.map_err(|e| {
+        tracing::warn!("Failed to fetch guilds page: {:?}", e);
+        e
+    })?;
+    let data_age = DataAge::of(&response);
+    let guilds = parse_guilds_page(response, world_name).await.map_err(|e| {
+        tracing::warn!("Failed to parse guilds page: {:?}", e);
+        e
+    })?;
+
+    Ok((data_age, guilds.len() as u32))
+}
+
+#[instrument(skip(client))]
+async fn get_active_house_auctions<S: Client>(
+    client: &S,
+    world_name: &str,
+    towns: &[String],
+) -> Result<(DataAge, u32), ServerError> {
+    // the towns are loaded on startup, without them there is nothing to scan
+    if towns.is_empty() {
+        tracing::warn!("No towns loaded, skipping house auctions of {world_name}");
+        return Err(anyhow::anyhow!("No towns loaded"))?;
+    }
+
+    let (data_age, houses) = get_residences(client, world_name, towns, &[ResidenceType::House])
+        .await
+        .map_err(|e| {
+            tracing::warn!("Failed to get houses of {world_name}: {:?}", e);
+            e
+        })?;
+    let active_auctions = houses
+        .iter()
+        .filter(|h| {
+            matches!(
+                h.status,
+                ResidenceStatus::AuctionNoBid | ResidenceStatus::AuctionWithBid { .. }
+            )
+        })
+        .count();
+
+    Ok((data_age, active_auctions as u32))
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        Path, State, WebSocketUpgrade,
+    },
+    response::Response,
+};
+use chrono::Utc;
+use tracing::instrument;
+
+use super::worlds_world_name::{parse_world_details_page, PathParams};
+use crate::{models::PlayersOnlineUpdate, prelude::*, AppState};
+
+/// How often the number of players online is fetched
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Streams the number of players online for a world, polling every 60 seconds
+#[instrument(name = "Stream Players Online", skip(state, ws))]
+pub async fn get<S: Client>(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState<S>>,
+    Path(path_params): Path<PathParams>,
+) -> Response {
+    let world_name = path_params.world_name();
+
+    ws.on_upgrade(move |socket| stream_players_online(socket, state.client, world_name))
+}
+
+async fn stream_players_online<S: Client>(mut socket: WebSocket, client: S, world_name: String) {
+    // the first tick completes immediately, so clients get a message right away
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let update = match get_players_online_update(&client, &world_name).await {
+                    Ok(update) => update,
+                    Err(ServerError::Client(TibiaError::NotFound)) => break,
+                    Err(e) => {
+                        tracing::warn!("Failed to get players online for {world_name}: {:?}", e);
+                        continue;
+                    }
+                };
+                let message = match serde_json::to_string(&update) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::error!("Failed to serialize players online update: {:?}", e);
+                        break;
+                    }
+                };
+                if socket.send(Message::Text(message)).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                match message {
+                    // the client disconnected
+                    None | Some(Err(_)) | Some(Ok(Message::Close(_))) => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+
+    tracing::info!("Players online stream for {world_name} closed");
+}
+
+#[instrument(skip(client))]
+async fn get_players_online_update<S: Client>(
+    client: &S,
+    world_name: &str,
+) -> Result<PlayersOnlineUpdate, ServerError> {
+    let response = client.fetch_world_details_page(world_name).await?;
+    let world_details = parse_world_details_page(response, world_name).await?;
+
+    Ok(PlayersOnlineUpdate {
+        players_online_count: world_details.players_online_count,
+        timestamp: Utc::now(),
+    })
+}
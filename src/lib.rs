@@ -1,36 +1,56 @@
 use std::{
+    future::Future,
     net::TcpListener,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use anyhow::Result;
-use axum::{body::Body, http::Request, routing::get, Router};
+use axum::{
+    handler::HandlerWithoutStateExt,
+    http::HeaderName,
+    routing::{get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
 use clients::Client;
+use futures::StreamExt;
 use prelude::TibiaClient;
-use reqwest::Method;
+use reqwest::{header, Method};
 use tower_http::{
-    classify::StatusInRangeAsFailures,
-    compression::CompressionLayer,
-    cors::{Any, CorsLayer},
-    services::ServeDir,
-    trace::TraceLayer,
+    classify::StatusInRangeAsFailures, compression::CompressionLayer, cors::CorsLayer,
+    services::ServeDir, trace::TraceLayer,
 };
-use tower_request_id::{RequestId, RequestIdLayer};
-use tracing::info_span;
+use tower_request_id::RequestIdLayer;
 
 pub mod clients;
 mod handlers;
 pub mod models;
 mod prelude;
+mod selectors;
 pub mod telemetry;
 mod utils;
 
-use utils::*;
+pub use utils::{cors::AllowedOrigins, data_age::DataAge};
+
+use utils::{
+    data_age::{DataAgeLayer, DATA_AGE_HEADER},
+    request_id::{RequestIdHeaderLayer, REQUEST_ID_HEADER},
+    TOTAL_COUNT_HEADER, *,
+};
 
 #[derive(Clone)]
 pub struct AppState<S: Client> {
     client: S,
     towns: Arc<Mutex<Vec<String>>>,
+    worlds_snapshot: Arc<Mutex<Option<handlers::worlds::WorldsSnapshot>>>,
+    /// When the worlds were last fetched from tibia.com successfully
+    last_successful_fetch: Arc<Mutex<Option<DateTime<Utc>>>>,
+    /// When [`warm_cache`] last finished fetching every world
+    cache_warmed_up_at: Arc<Mutex<Option<DateTime<Utc>>>>,
+    known_worlds: Arc<Mutex<Option<handlers::worlds::KnownWorlds>>>,
+    /// The parsed results of recent requests
+    results: result_cache::ResultCache,
 }
 
 impl AppState<TibiaClient> {
@@ -38,6 +58,40 @@ impl AppState<TibiaClient> {
         AppState {
             client,
             towns: Arc::new(Mutex::new(vec![])),
+            worlds_snapshot: Arc::new(Mutex::new(None)),
+            last_successful_fetch: Arc::new(Mutex::new(None)),
+            cache_warmed_up_at: Arc::new(Mutex::new(None)),
+            known_worlds: Arc::new(Mutex::new(None)),
+            results: result_cache::ResultCache::new(),
+        }
+    }
+}
+
+impl<S: Client> AppState<S> {
+    /// Keeps the names of the worlds, to reject requests for other worlds,
+    /// and when they were fetched, to tell how stale the data is
+    fn remember_worlds(&self, data_age: DataAge, worlds: &[models::World]) {
+        let known_worlds = handlers::worlds::KnownWorlds::new(worlds, Utc::now());
+        self.known_worlds.lock().unwrap().replace(known_worlds);
+
+        let fetched_at = Utc::now() - chrono::Duration::seconds(data_age.seconds() as i64);
+        let mut last_successful_fetch = self.last_successful_fetch.lock().unwrap();
+        // a page from the cache can be older than the last one fetched
+        if *last_successful_fetch < Some(fetched_at) {
+            last_successful_fetch.replace(fetched_at);
+        }
+    }
+
+    /// Fails with [`ServerError::not_found`](prelude::ServerError::not_found) for
+    /// worlds missing from the recently fetched worlds, without asking tibia.com
+    fn ensure_world_exists(&self, world_name: &str) -> Result<(), prelude::ServerError> {
+        let known_worlds = self.known_worlds.lock().unwrap();
+        match known_worlds.as_ref() {
+            Some(known_worlds) if known_worlds.is_unknown(world_name, Utc::now()) => {
+                tracing::info!("World '{}' not found in the known worlds", world_name);
+                Err(prelude::ServerError::not_found())
+            }
+            _ => Ok(()),
         }
     }
 }
@@ -47,18 +101,29 @@ impl Default for AppState<TibiaClient> {
         Self {
             client: TibiaClient::default(),
             towns: Arc::new(Mutex::new(vec![])),
+            worlds_snapshot: Arc::new(Mutex::new(None)),
+            last_successful_fetch: Arc::new(Mutex::new(None)),
+            cache_warmed_up_at: Arc::new(Mutex::new(None)),
+            known_worlds: Arc::new(Mutex::new(None)),
+            results: result_cache::ResultCache::new(),
         }
     }
 }
 
-pub fn app<C: Client>(state: AppState<C>) -> Router {
-    let openapi_docs = openapi::create_openapi_docs();
+pub fn app<C: Client>(state: AppState<C>, allowed_origins: AllowedOrigins) -> Router {
+    let openapi_docs = Arc::new(openapi::create_versioned_openapi_docs(
+        &openapi::public_base_url(),
+    ));
 
-    let public_service = ServeDir::new("public");
+    let public_service =
+        ServeDir::new("public").not_found_service(handlers::fallback::get.into_service());
 
     let app = Router::new()
+        .route("/api/v1/news.rss", get(handlers::news_rss::get))
+        .route("/api/v1/spells", get(handlers::spells::get))
         .route("/api/v1/towns", get(handlers::towns::get))
         .route("/api/v1/worlds", get(handlers::worlds::get))
+        .route("/api/v1/worlds/compare", get(handlers::worlds_compare::get))
         .route(
             "/api/v1/worlds/:world_name",
             get(handlers::worlds_world_name::get),
@@ -67,66 +132,157 @@ pub fn app<C: Client>(state: AppState<C>) -> Router {
             "/api/v1/worlds/:world_name/guilds",
             get(handlers::worlds_world_name_guilds::get),
         )
+        .route(
+            "/api/v1/worlds/:world_name/guilds/:guild_name",
+            get(handlers::worlds_world_name_guilds_guild_name::get),
+        )
+        .route(
+            "/api/v1/worlds/:world_name/guilds/:guild_name/members",
+            get(handlers::worlds_world_name_guilds_guild_name_members::get),
+        )
+        .route(
+            "/api/v1/worlds/:world_name/highscores/:category",
+            get(handlers::worlds_world_name_highscores_category::get),
+        )
         .route(
             "/api/v1/worlds/:world_name/kill-statistics",
             get(handlers::worlds_world_name_kill_statistics::get),
         )
+        .route(
+            "/api/v1/worlds/:world_name/kill-statistics/:race",
+            get(handlers::worlds_world_name_kill_statistics_race::get),
+        )
+        .route(
+            "/api/v1/worlds/:world_name/online-players",
+            get(handlers::worlds_world_name_online_players::get),
+        )
+        .route(
+            "/api/v1/worlds/:world_name/players-online/record",
+            get(handlers::worlds_world_name_players_online_record::get),
+        )
+        // kept for clients of the path the record was first served under
+        .route(
+            "/api/v1/worlds/:world_name/online-players/record",
+            get(handlers::worlds_world_name_players_online_record::get),
+        )
+        .route(
+            "/api/v1/worlds/:world_name/quest-titles",
+            get(handlers::worlds_world_name_quest_titles::get),
+        )
         .route(
             "/api/v1/worlds/:world_name/residences",
             get(handlers::worlds_world_name_residences::get),
         )
+        .route(
+            "/api/v1/worlds/:world_name/summary",
+            get(handlers::worlds_world_name_summary::get),
+        )
+        .route(
+            "/api/v1/guilds/:guild_name",
+            get(handlers::guilds_guild_name::get),
+        )
+        .route(
+            "/api/v1/residences/auctions",
+            get(handlers::residences_auctions::get),
+        )
+        .route("/api/v1/bazaar", get(handlers::bazaar::get))
+        .route("/api/v1/server-save", get(handlers::server_save::get))
+        .route("/api/v1/characters", post(handlers::characters::post))
+        .route(
+            "/api/v1/characters/:character_name",
+            get(handlers::characters_character_name::get),
+        )
+        .route(
+            "/api/v1/characters/:character_name/deaths",
+            get(handlers::characters_character_name_deaths::get),
+        )
+        .route(
+            "/api/v1/creatures/:creature_name",
+            get(handlers::creatures_creature_name::get),
+        )
+        .route(
+            "/ws/v1/worlds/:world_name/players-online",
+            get(handlers::ws_worlds_world_name_players_online::get),
+        )
         .route("/", get(handlers::redocly::redirect_redocly))
         .route("/api-docs", get(handlers::redocly::serve_redocly))
         .route("/__healthcheck", get(handlers::__healthcheck::get))
+        .route("/__version", get(handlers::__version::get))
         .fallback_service(public_service)
         .with_state(state);
 
-    app.route("/openapi.json", get(handlers::redocly::serve_openapi))
+    app.route("/api/v1", get(handlers::index::get))
+        .route("/openapi.json", get(handlers::redocly::serve_openapi))
+        .route(
+            "/openapi/:file_name",
+            get(handlers::redocly::serve_versioned_openapi),
+        )
         .with_state(openapi_docs)
+        .layer(DataAgeLayer)
         .layer(CompressionLayer::new())
         .layer(
-            CorsLayer::new()
-                // allow `GET` and `POST` when accessing the resource
-                .allow_methods([Method::GET])
-                // allow requests from any origin
-                .allow_origin(Any),
+            allowed_origins.apply(
+                CorsLayer::new()
+                    // allow `GET` and `POST` when accessing the resource
+                    .allow_methods([Method::GET, Method::POST])
+                    // allow json bodies for `POST`
+                    .allow_headers([header::CONTENT_TYPE])
+                    // allow reading how old the data is, when to retry, the request id
+                    // and the total count of paginated lists
+                    .expose_headers([
+                        HeaderName::from_static(DATA_AGE_HEADER),
+                        HeaderName::from_static(REQUEST_ID_HEADER),
+                        HeaderName::from_static(TOTAL_COUNT_HEADER),
+                        header::RETRY_AFTER,
+                    ]),
+            ),
         )
         .layer(
             TraceLayer::new(StatusInRangeAsFailures::new(400..=599).into_make_classifier())
                 // Let's create a tracing span for each request
-                .make_span_with(|request: &Request<Body>| {
-                    // We get the request id from the extensions
-                    let request_id = request
-                        .extensions()
-                        .get::<RequestId>()
-                        .map(ToString::to_string)
-                        .unwrap_or_else(|| "unknown".into());
-                    // And then we put it along with other information into the `request` span
-                    info_span!(
-                        "request",
-                        id = %request_id,
-                        method = %request.method(),
-                        uri = %request.uri(),
-                    )
-                })
-                .on_response(
-                    tower_http::trace::DefaultOnResponse::new().level(tracing::Level::INFO),
-                ),
+                .make_span_with(trace::make_span)
+                .on_response(trace::on_response),
         )
+        .layer(RequestIdHeaderLayer)
         .layer(RequestIdLayer)
 }
 
+/// How long in-flight requests may take to finish after a shutdown signal,
+/// unless configured through `SHUTDOWN_TIMEOUT_SECS`
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long in-flight requests may take to finish after a shutdown signal,
+/// in seconds from `SHUTDOWN_TIMEOUT_SECS` or 30 seconds
+pub fn shutdown_timeout() -> Duration {
+    std::env::var("SHUTDOWN_TIMEOUT_SECS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT)
+}
+
 pub async fn run(app: Router, listener: TcpListener) -> Result<()> {
+    run_until(app, listener, shutdown_signal(), shutdown_timeout()).await
+}
+
+/// Serves the app until `shutdown` completes, then stops accepting connections
+/// and gives the in-flight requests up to `drain_timeout` to finish
+pub async fn run_until(
+    app: Router,
+    listener: TcpListener,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+    drain_timeout: Duration,
+) -> Result<()> {
     let addr = listener.local_addr()?;
 
     tracing::info!("Listening on {}", addr);
 
+    let (signalled_tx, signalled_rx) = tokio::sync::oneshot::channel();
     let server = axum::Server::from_tcp(listener)?
         .serve(app.into_make_service())
-        .with_graceful_shutdown(async {
-            tokio::signal::ctrl_c()
-                .await
-                .expect("Failed to install CTRL+C signal handler");
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            let _ = signalled_tx.send(());
         });
 
     // Fills state with towns
@@ -134,7 +290,113 @@ pub async fn run(app: Router, listener: TcpListener) -> Result<()> {
         let _ = reqwest::get(format!("http://{addr}/api/v1/towns")).await;
     });
 
-    server.await?;
+    tokio::pin!(server);
+    tokio::select! {
+        result = &mut server => return Ok(result?),
+        _ = signalled_rx => {}
+    }
+
+    tracing::info!(
+        "Waiting up to {}s for in-flight requests to finish",
+        drain_timeout.as_secs()
+    );
+    match tokio::time::timeout(drain_timeout, server).await {
+        Ok(result) => {
+            result?;
+            tracing::info!("Finished the in-flight requests");
+        }
+        Err(_) => {
+            tracing::warn!(
+                "Requests still in flight after {}s, shutting down anyway",
+                drain_timeout.as_secs()
+            );
+        }
+    }
 
     Ok(())
 }
+
+/// Keeps the details of every world cached, so that the first requests for
+/// them don't have to wait for tibia.com.
+///
+/// The parsed details are refreshed in the results cache, and the pages they
+/// are parsed from in the cache of the client.
+pub async fn warm_cache<C: Client>(state: AppState<C>) {
+    // refresh the worlds halfway through their time in the cache
+    let period = (clients::cache_ttl() / 2).max(Duration::from_secs(1));
+    let mut interval = tokio::time::interval(period);
+
+    loop {
+        interval.tick().await;
+
+        let worlds = match handlers::worlds::get_worlds(&state.client).await {
+            Ok((data_age, worlds)) => {
+                state.remember_worlds(data_age, &worlds.worlds);
+                worlds.worlds
+            }
+            Err(e) => {
+                tracing::warn!("Failed to warm up the cache: {:?}", e);
+                continue;
+            }
+        };
+
+        let state = &state;
+        let failures = futures::stream::iter(worlds)
+            .map(|world| async move {
+                handlers::worlds_world_name::get_cached_world_details(state, &world.name, true)
+                    .await
+                    .is_err()
+            })
+            .buffer_unordered(5)
+            .filter(|failed| futures::future::ready(*failed))
+            .count()
+            .await;
+        if failures > 0 {
+            tracing::warn!("Failed to warm up {failures} worlds");
+        }
+
+        tracing::info!("Warmed up the cache");
+        state.cache_warmed_up_at.lock().unwrap().replace(Utc::now());
+    }
+}
+
+/// Keeps the names of the worlds fresh, so that requests for unknown worlds
+/// can be answered without fetching them
+pub async fn refresh_known_worlds<C: Client>(state: AppState<C>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(30 * 60));
+
+    loop {
+        interval.tick().await;
+
+        match handlers::worlds::get_worlds(&state.client).await {
+            Ok((data_age, worlds)) => state.remember_worlds(data_age, &worlds.worlds),
+            Err(e) => tracing::warn!("Failed to refresh the known worlds: {:?}", e),
+        }
+    }
+}
+
+/// Resolves on CTRL+C, or on SIGTERM which containers are stopped with
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install CTRL+C signal handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM signal handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutting down gracefully");
+}
@@ -1,6 +1,6 @@
-use anyhow::Result;
-use std::net::TcpListener;
-use tibia_api::{telemetry, AppState};
+use anyhow::{Context, Result};
+use std::net::{IpAddr, SocketAddr, TcpListener};
+use tibia_api::{telemetry, AllowedOrigins, AppState};
 use tracing_appender::rolling;
 use tracing_subscriber::fmt::writer::MakeWriterExt;
 
@@ -12,12 +12,27 @@ async fn main() -> Result<()> {
     let subscriber = telemetry::get_subscriber("tibia_api".into(), "info".into(), sink);
     telemetry::init_subscriber(subscriber);
 
+    let host = std::env::var("HOST").unwrap_or("0.0.0.0".to_string());
+    let host: IpAddr = host
+        .parse()
+        .with_context(|| format!("HOST must be an ip address, got '{host}'"))?;
     let port = std::env::var("PORT").unwrap_or("3000".to_string());
+    let port: u16 = port
+        .parse()
+        .with_context(|| format!("PORT must be a port number, got '{port}'"))?;
 
-    let listener = TcpListener::bind(format!("0.0.0.0:{port}"))?;
+    let listener = TcpListener::bind(SocketAddr::new(host, port))?;
 
-    let app = tibia_api::app(AppState::default());
-    tibia_api::run(app, listener).await?;
+    let state = AppState::default();
+    tokio::spawn(tibia_api::refresh_known_worlds(state.clone()));
+    // Off by default, to not put extra load on tibia.com
+    if std::env::var("WARM_CACHE_ON_STARTUP").is_ok_and(|v| v == "true") {
+        tokio::spawn(tibia_api::warm_cache(state.clone()));
+    }
 
-    Ok(())
+    let app = tibia_api::app(state, AllowedOrigins::from_env());
+    let result = tibia_api::run(app, listener).await;
+    telemetry::shutdown().await;
+
+    result
 }
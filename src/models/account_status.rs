@@ -0,0 +1,22 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountStatus {
+    Free,
+    Premium,
+}
+
+impl std::str::FromStr for AccountStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Free Account" => Ok(AccountStatus::Free),
+            "Premium Account" => Ok(AccountStatus::Premium),
+            _ => Err(anyhow!("Unexpected account status: '{}'", s)),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How long a world has been protected by BattlEye
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BattlEyeType {
+    /// Not protected by BattlEye
+    Unprotected,
+    /// Protected by BattlEye since the world was released
+    SinceRelease,
+    /// Protected by BattlEye since `battlEyeDate`
+    SinceDate,
+}
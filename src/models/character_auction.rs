@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{Sex, Vocation};
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterAuction {
+    /// The id of the auction (auctionid)
+    #[schema(example = 1234567)]
+    pub id: u32,
+    #[schema(example = "Urinchoklad")]
+    pub name: String,
+    #[schema(example = 52)]
+    pub level: u16,
+    pub vocation: Option<Vocation>,
+    pub sex: Sex,
+    #[schema(example = "Antica")]
+    pub world: String,
+    /// The current bid in Tibia Coins, or the minimum bid if nobody has bid yet
+    #[schema(example = 2500)]
+    pub bid: u32,
+    /// Whether anyone has bid on the auction yet
+    pub has_bid: bool,
+    /// When the auction started
+    #[schema(value_type = String, format = DateTime)]
+    pub started_at: DateTime<Utc>,
+    /// When the auction ends
+    #[schema(value_type = String, format = DateTime)]
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Filters applied by Tibia when listing auctions
+#[derive(Debug, Default, Clone)]
+pub struct BazaarFilters {
+    pub world: Option<String>,
+    pub vocation: Option<Vocation>,
+    pub min_level: Option<u16>,
+}
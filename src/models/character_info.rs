@@ -0,0 +1,118 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{AccountStatus, Sex, Vocation};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterHouse {
+    /// The id of the residence (houseid)
+    #[schema(example = 50101)]
+    pub id: u32,
+    #[schema(example = "Castle Shop 1")]
+    pub name: String,
+    #[schema(example = "Edron")]
+    pub town: String,
+    /// The date the rent is paid until
+    #[schema(value_type = String, format = Date)]
+    pub paid_until: NaiveDate,
+}
+
+/// The look of the character, as used to render outfit images
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterOutfit {
+    #[schema(example = 129)]
+    pub look_type: u16,
+    #[schema(example = 95)]
+    pub head: u8,
+    #[schema(example = 116)]
+    pub body: u8,
+    #[schema(example = 121)]
+    pub legs: u8,
+    #[schema(example = 115)]
+    pub feet: u8,
+    /// The addons worn, 1 and 2 for either addon and 3 for both
+    #[schema(example = 3)]
+    pub addons: u8,
+    /// The look type of the mount, if riding one
+    #[schema(example = 368)]
+    pub mount: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterGuild {
+    #[schema(example = "Red Rose")]
+    pub name: String,
+    #[schema(example = "Leader")]
+    pub rank: String,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterInfo {
+    #[schema(example = "Urinchoklad")]
+    pub name: String,
+    /// Names the character has had before
+    pub former_names: Vec<String>,
+    /// The currently selected title
+    #[schema(example = "Aspiring Huntsman")]
+    pub title: Option<String>,
+    /// The number of titles the character has unlocked
+    #[schema(example = 7)]
+    pub titles_unlocked: u8,
+    /// The title earned through loyalty points from premium time
+    #[schema(example = "Guardian of Tibia")]
+    pub loyalty_title: Option<String>,
+    pub sex: Sex,
+    /// The character's vocation, if it has one
+    pub vocation: Option<Vocation>,
+    #[schema(example = 52)]
+    pub level: u16,
+    #[schema(example = 23)]
+    pub achievement_points: u32,
+    #[schema(example = "Antica")]
+    pub world: String,
+    /// Relative url of the character's world
+    #[schema(example = "/api/v1/worlds/Antica")]
+    pub world_url: String,
+    /// The world the character was on before its last transfer
+    #[schema(example = "Secura")]
+    pub former_world: Option<String>,
+    /// Relative url of the character's former world
+    #[schema(example = "/api/v1/worlds/Secura")]
+    pub former_world_url: Option<String>,
+    /// The town the character lives and respawns in
+    #[schema(example = "Thais")]
+    pub residence: String,
+    /// The number of consecutive days the daily reward has been collected,
+    /// unless hidden by the character's privacy settings
+    #[schema(example = 42)]
+    pub daily_reward_streak: Option<u32>,
+    /// Whether the character has bought the charm expansion
+    #[schema(example = true)]
+    pub charm_expansion: Option<bool>,
+    /// The points earned from hunting tasks
+    #[schema(example = 5000)]
+    pub hunting_task_points: Option<u32>,
+    pub outfit: Option<CharacterOutfit>,
+    /// The animated image of the character's outfit
+    #[schema(
+        example = "https://outfit-images.ots.me/animatedOutfits1099/animoutfit.php?id=129&addons=3&head=95&body=116&legs=121&feet=115&mount=368"
+    )]
+    pub outfit_url: Option<String>,
+    /// Houses owned by the character
+    pub houses: Vec<CharacterHouse>,
+    pub guild: Option<CharacterGuild>,
+    /// The last time the character logged in, `null` if it never has
+    #[serialize_always]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub last_login: Option<DateTime<Utc>>,
+    pub comment: Option<String>,
+    /// Whether the character's account is a free or premium account
+    pub account_status: AccountStatus,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::CharacterInfo;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CharacterLookupError {
+    NotFound,
+}
+
+/// The outcome of looking up a single character in a batch
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum CharacterResult {
+    Found {
+        /// The requested name
+        #[schema(example = "Urinchoklad")]
+        name: String,
+        data: Box<CharacterInfo>,
+    },
+    Error {
+        /// The requested name
+        #[schema(example = "Hälge")]
+        name: String,
+        error: CharacterLookupError,
+    },
+}
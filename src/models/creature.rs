@@ -0,0 +1,23 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Creature {
+    #[schema(example = "Dragon Lord")]
+    pub name: String,
+    #[schema(example = 1900)]
+    pub hitpoints: u32,
+    /// The experience gained for killing the creature
+    #[schema(example = 2100)]
+    pub experience: u32,
+    /// The damage taken per element in percent,
+    /// e.g. 0 means immune and above 100 means weak.
+    #[schema(example = json!({ "physical": 100, "fire": 0, "ice": 110 }))]
+    pub resistances: BTreeMap<String, u16>,
+    /// The items the creature may drop
+    #[schema(example = json!(["gold coin", "royal helmet"]))]
+    pub loot: Vec<String>,
+}
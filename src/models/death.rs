@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A killer involved in a character's death
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DeathKiller {
+    /// Another character
+    Player {
+        #[schema(example = "Mad Bubble")]
+        name: String,
+    },
+    /// A creature, boss or other non-player source
+    Creature {
+        #[schema(example = "dragon lord")]
+        name: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Death {
+    /// When the character died
+    #[schema(value_type = String, format = DateTime)]
+    pub time: DateTime<Utc>,
+    /// The level the character died at
+    #[schema(example = 52)]
+    pub level: u16,
+    /// Everyone who took part in the kill, in the order tibia.com lists them
+    pub killers: Vec<DeathKiller>,
+}
@@ -1,14 +1,30 @@
 use anyhow::Result;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum GameWorldType {
     Regular,
     Experimental,
 }
 
+impl GameWorldType {
+    /// The name as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameWorldType::Regular => "regular",
+            GameWorldType::Experimental => "experimental",
+        }
+    }
+}
+
+impl std::fmt::Display for GameWorldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl std::str::FromStr for GameWorldType {
     type Err = anyhow::Error;
 
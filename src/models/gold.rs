@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// An amount of gold coins, serialized as a plain number
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Gold(pub u32);
+
+impl Gold {
+    /// The number of gold coins
+    pub fn amount(&self) -> u32 {
+        self.0
+    }
+
+    /// The amount written the way players do, such as `400k` or `1.55kk`.
+    ///
+    /// Decimals are truncated rather than rounded, so the amount is never overstated.
+    pub fn to_kk_string(&self) -> String {
+        match self.0 {
+            amount if amount >= 1_000_000 => compact(amount, 1_000_000, "kk"),
+            amount if amount >= 1_000 => compact(amount, 1_000, "k"),
+            amount => amount.to_string(),
+        }
+    }
+
+    /// The amount with thousands separators, such as `1,555,555`
+    pub fn to_separated_string(&self) -> String {
+        let digits = self.0.to_string();
+        let mut groups = digits
+            .as_bytes()
+            .rchunks(3)
+            .map(|group| std::str::from_utf8(group).expect("digits are ascii"))
+            .collect::<Vec<_>>();
+        groups.reverse();
+
+        groups.join(",")
+    }
+}
+
+/// Writes `amount` in `unit`s with at most two decimals
+fn compact(amount: u32, unit: u32, suffix: &str) -> String {
+    let whole = amount / unit;
+    let hundredths = (amount % unit) / (unit / 100);
+
+    match hundredths {
+        0 => format!("{whole}{suffix}"),
+        h if h % 10 == 0 => format!("{whole}.{}{suffix}", h / 10),
+        h => format!("{whole}.{h:02}{suffix}"),
+    }
+}
+
+impl From<u32> for Gold {
+    fn from(amount: u32) -> Self {
+        Gold(amount)
+    }
+}
+
+impl From<Gold> for u32 {
+    fn from(gold: Gold) -> Self {
+        gold.0
+    }
+}
+
+impl std::fmt::Display for Gold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} gold", self.to_separated_string())
+    }
+}
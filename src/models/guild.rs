@@ -1,16 +1,30 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 #[serde_with::skip_serializing_none]
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Guild {
     /// The guild's logo URL.
     pub logo: Option<String>,
     /// The guild name
     pub name: String,
+    /// The relative url of the guild on this api
+    #[schema(example = "/api/v1/worlds/Antica/guilds/Red%20Rose")]
+    pub guild_url: String,
+    /// The url of the guild on tibia.com
+    #[schema(
+        example = "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Red%20Rose"
+    )]
+    pub tibia_url: String,
     /// The guild's description (preserved formatting)
     pub description: Option<String>,
     /// Whether the guild is still in formation or not
     pub active: bool,
+    /// Whether the guild is currently at war.
+    /// Only known when requested with `includeWarStatus=true`.
+    pub at_war: Option<bool>,
+    /// Whether the guild accepts applications.
+    /// Only known when requested with `includeWarStatus=true`.
+    pub application_open: Option<bool>,
 }
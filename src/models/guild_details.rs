@@ -0,0 +1,150 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{PaginatedResponse, Vocation};
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GuildMember {
+    #[schema(example = "Urinchoklad")]
+    pub name: String,
+    /// The member's title within the guild
+    #[schema(example = "Dragon Slayer")]
+    pub title: Option<String>,
+    #[schema(example = "Leader")]
+    pub rank: String,
+    pub vocation: Option<Vocation>,
+    #[schema(example = 52)]
+    pub level: u32,
+    /// The date the character joined the guild
+    #[schema(value_type = String, format = Date)]
+    pub joined: NaiveDate,
+    pub is_online: bool,
+}
+
+/// The state of a war between two guilds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum GuildWarStatus {
+    War,
+    Truce,
+}
+
+impl std::str::FromStr for GuildWarStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "war" => Ok(GuildWarStatus::War),
+            "truce" => Ok(GuildWarStatus::Truce),
+            _ => Err(anyhow::anyhow!("Unexpected guild war status: '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GuildWar {
+    #[schema(example = "Red Rose")]
+    pub opponent_guild: String,
+    /// Kills by the opponent on members of this guild
+    #[schema(example = 9)]
+    pub kills_us: u32,
+    /// Kills by members of this guild on the opponent
+    #[schema(example = 14)]
+    pub kills_them: u32,
+    pub status: GuildWarStatus,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GuildDetails {
+    #[schema(example = "Red Rose")]
+    pub name: String,
+    /// The relative url of the guild on this api.
+    /// Only known when the guild was requested through its world.
+    #[schema(example = "/api/v1/worlds/Antica/guilds/Red%20Rose")]
+    pub guild_url: Option<String>,
+    /// The url of the guild on tibia.com
+    #[schema(
+        example = "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Red%20Rose"
+    )]
+    pub tibia_url: String,
+    /// The guild's logo URL
+    pub logo: Option<String>,
+    /// The guild's description
+    #[schema(example = "Hunting dragons together since 2019.")]
+    pub description: Option<String>,
+    /// Whether the guild accepts applications
+    pub application_open: bool,
+    /// The date the guild was founded
+    #[schema(value_type = String, format = Date)]
+    pub created_at: NaiveDate,
+    #[schema(example = "Urinchoklad")]
+    pub founder: String,
+    /// The number of members in the guild
+    #[schema(example = 5)]
+    pub member_count: u32,
+    pub members: Vec<GuildMember>,
+    /// The wars the guild is currently involved in
+    pub wars: Vec<GuildWar>,
+}
+
+/// The guild details with a single page of its members
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedGuildDetails {
+    #[schema(example = "Red Rose")]
+    pub name: String,
+    /// The relative url of the guild on this api.
+    /// Only known when the guild was requested through its world.
+    #[schema(example = "/api/v1/worlds/Antica/guilds/Red%20Rose")]
+    pub guild_url: Option<String>,
+    /// The url of the guild on tibia.com
+    #[schema(
+        example = "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Red%20Rose"
+    )]
+    pub tibia_url: String,
+    /// The guild's logo URL
+    pub logo: Option<String>,
+    /// The guild's description
+    #[schema(example = "Hunting dragons together since 2019.")]
+    pub description: Option<String>,
+    /// Whether the guild accepts applications
+    pub application_open: bool,
+    /// The date the guild was founded
+    #[schema(value_type = String, format = Date)]
+    pub created_at: NaiveDate,
+    #[schema(example = "Urinchoklad")]
+    pub founder: String,
+    /// The number of members in the guild
+    #[schema(example = 5)]
+    pub member_count: u32,
+    #[schema(value_type = PaginatedGuildMembers)]
+    pub members: PaginatedResponse<GuildMember>,
+    /// The wars the guild is currently involved in
+    pub wars: Vec<GuildWar>,
+}
+
+impl PaginatedGuildDetails {
+    /// Picks out the requested page of the guild's members
+    pub fn paginate(guild: GuildDetails, page: u32, per_page: u32) -> Self {
+        Self {
+            name: guild.name,
+            guild_url: guild.guild_url,
+            tibia_url: guild.tibia_url,
+            logo: guild.logo,
+            description: guild.description,
+            application_open: guild.application_open,
+            created_at: guild.created_at,
+            founder: guild.founder,
+            member_count: guild.member_count,
+            members: PaginatedResponse::paginate(guild.members, page, per_page),
+            wars: guild.wars,
+        }
+    }
+}
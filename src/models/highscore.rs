@@ -0,0 +1,104 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::Vocation;
+
+/// What the characters are ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum HighscoreCategory {
+    Experience,
+    MagicLevel,
+    Sword,
+    Axe,
+    Club,
+    Distance,
+    Shield,
+    Fist,
+    Fishing,
+    Achievements,
+    LoyaltyPoints,
+}
+
+impl HighscoreCategory {
+    /// The name as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HighscoreCategory::Experience => "experience",
+            HighscoreCategory::MagicLevel => "magicLevel",
+            HighscoreCategory::Sword => "sword",
+            HighscoreCategory::Axe => "axe",
+            HighscoreCategory::Club => "club",
+            HighscoreCategory::Distance => "distance",
+            HighscoreCategory::Shield => "shield",
+            HighscoreCategory::Fist => "fist",
+            HighscoreCategory::Fishing => "fishing",
+            HighscoreCategory::Achievements => "achievements",
+            HighscoreCategory::LoyaltyPoints => "loyaltyPoints",
+        }
+    }
+
+    /// The id tibia.com uses for the category
+    pub fn tibia_id(&self) -> u8 {
+        match self {
+            HighscoreCategory::Achievements => 1,
+            HighscoreCategory::Axe => 2,
+            HighscoreCategory::Club => 4,
+            HighscoreCategory::Distance => 5,
+            HighscoreCategory::Experience => 6,
+            HighscoreCategory::Fishing => 7,
+            HighscoreCategory::Fist => 8,
+            HighscoreCategory::LoyaltyPoints => 10,
+            HighscoreCategory::MagicLevel => 11,
+            HighscoreCategory::Shield => 12,
+            HighscoreCategory::Sword => 13,
+        }
+    }
+}
+
+impl std::str::FromStr for HighscoreCategory {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "experience" => Ok(HighscoreCategory::Experience),
+            "magicLevel" => Ok(HighscoreCategory::MagicLevel),
+            "sword" => Ok(HighscoreCategory::Sword),
+            "axe" => Ok(HighscoreCategory::Axe),
+            "club" => Ok(HighscoreCategory::Club),
+            "distance" => Ok(HighscoreCategory::Distance),
+            "shield" => Ok(HighscoreCategory::Shield),
+            "fist" => Ok(HighscoreCategory::Fist),
+            "fishing" => Ok(HighscoreCategory::Fishing),
+            "achievements" => Ok(HighscoreCategory::Achievements),
+            "loyaltyPoints" => Ok(HighscoreCategory::LoyaltyPoints),
+            _ => Err(anyhow!(
+                "Invalid highscore category '{}', expected one of: experience, magicLevel, sword, axe, club, distance, shield, fist, fishing, achievements, loyaltyPoints",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for HighscoreCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HighscoreEntry {
+    #[schema(example = 1)]
+    pub rank: u32,
+    #[schema(example = "Urinchoklad")]
+    pub name: String,
+    pub vocation: Option<Vocation>,
+    #[schema(example = 52)]
+    pub level: u32,
+    /// The points or skill level of the requested category
+    #[schema(example = 2_417_300)]
+    pub value: u64,
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct KilledAmounts {
     /// The amount of players killed by monsters
@@ -10,6 +10,22 @@ pub struct KilledAmounts {
     pub killed_by_players: u32,
 }
 
+impl KilledAmounts {
+    /// The amount of players and monsters killed
+    pub fn total(&self) -> u32 {
+        self.killed_players + self.killed_by_players
+    }
+}
+
+impl<'a> std::iter::Sum<&'a KilledAmounts> for KilledAmounts {
+    fn sum<I: Iterator<Item = &'a KilledAmounts>>(iter: I) -> Self {
+        iter.fold(KilledAmounts::default(), |sum, amounts| KilledAmounts {
+            killed_players: sum.killed_players + amounts.killed_players,
+            killed_by_players: sum.killed_by_players + amounts.killed_by_players,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RaceKillStatistics {
@@ -19,6 +35,29 @@ pub struct RaceKillStatistics {
     pub last_day: KilledAmounts,
     /// The aggregated kill statistics for the last week
     pub last_week: KilledAmounts,
+    /// The amount of players killed by monsters per day, averaged over the last week
+    #[schema(example = 7.29)]
+    pub daily_average_killed_players: f64,
+    /// The amount of monsters killed by players per day, averaged over the last week
+    #[schema(example = 1176.14)]
+    pub daily_average_killed_by_players: f64,
+}
+
+impl RaceKillStatistics {
+    pub fn new(race: String, last_day: KilledAmounts, last_week: KilledAmounts) -> Self {
+        Self {
+            daily_average_killed_players: daily_average(last_week.killed_players),
+            daily_average_killed_by_players: daily_average(last_week.killed_by_players),
+            race,
+            last_day,
+            last_week,
+        }
+    }
+}
+
+/// The average per day of an amount over a week, rounded to two decimals
+fn daily_average(weekly_amount: u32) -> f64 {
+    (f64::from(weekly_amount) / 7.0 * 100.0).round() / 100.0
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -26,6 +65,62 @@ pub struct RaceKillStatistics {
 pub struct KillStatistics {
     pub total_last_day: KilledAmounts,
     pub total_last_week: KilledAmounts,
+    /// The amount of players killed by any race during the last day
+    #[schema(example = 850)]
+    pub total_deaths_last_day: u32,
+    /// The amount of players killed by any race during the last week
+    #[schema(example = 4830)]
+    pub total_deaths_last_week: u32,
+    /// The sum of the races' statistics for the last day, to cross-check `totalLastDay`
+    pub races_total_last_day: KilledAmounts,
+    /// The sum of the races' statistics for the last week, to cross-check `totalLastWeek`
+    pub races_total_last_week: KilledAmounts,
+    /// The amount of races listed
+    #[schema(example = 1250)]
+    pub race_count: u32,
     /// A list of kill statistics for each race
     pub races: Vec<RaceKillStatistics>,
 }
+
+/// The order of the races, by their statistics of the last day
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum KillStatSort {
+    /// Alphabetical, as listed on tibia.com
+    #[default]
+    RaceAsc,
+    RaceDesc,
+    KilledPlayersDesc,
+    KilledByPlayersDesc,
+    /// By players and monsters killed together
+    TotalKillsDesc,
+}
+
+impl KillStatSort {
+    pub fn sort(&self, races: &mut [RaceKillStatistics]) {
+        match self {
+            KillStatSort::RaceAsc => races.sort_by(|a, b| a.race.cmp(&b.race)),
+            KillStatSort::RaceDesc => races.sort_by(|a, b| b.race.cmp(&a.race)),
+            KillStatSort::KilledPlayersDesc => {
+                races.sort_by_key(|r| std::cmp::Reverse(r.last_day.killed_players))
+            }
+            KillStatSort::KilledByPlayersDesc => {
+                races.sort_by_key(|r| std::cmp::Reverse(r.last_day.killed_by_players))
+            }
+            KillStatSort::TotalKillsDesc => {
+                races.sort_by_key(|r| std::cmp::Reverse(r.last_day.total()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RaceKillStatisticsWithTotals {
+    #[serde(flatten)]
+    pub race: RaceKillStatistics,
+    /// The kill statistics of every race for the last day
+    pub total_last_day: KilledAmounts,
+    /// The kill statistics of every race for the last week
+    pub total_last_week: KilledAmounts,
+}
@@ -1,9 +1,9 @@
 use anyhow::{anyhow, Result};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 /// The location where the server is hosted
-#[derive(Serialize, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum Location {
     Europe,
@@ -12,6 +12,24 @@ pub enum Location {
     Oceania,
 }
 
+impl Location {
+    /// The name as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Location::Europe => "europe",
+            Location::SouthAmerica => "southAmerica",
+            Location::NorthAmerica => "northAmerica",
+            Location::Oceania => "oceania",
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl std::str::FromStr for Location {
     type Err = anyhow::Error;
 
@@ -1,23 +1,182 @@
+mod account_status;
+mod battl_eye_type;
+mod character_auction;
+mod character_info;
+mod character_result;
+mod creature;
+mod death;
 mod game_world_type;
+mod gold;
 mod guild;
+mod guild_details;
+mod highscore;
 mod kill_statistics;
 mod location;
+mod news;
+mod online_record;
+mod online_status;
+mod paginated_response;
 mod player;
+mod players_online_update;
 mod pvp_type;
 mod residence;
+mod response_format;
+mod server_save;
+mod sex;
+mod spell;
 mod transfer_type;
 mod vocation;
+mod world_comparison;
 mod world_details;
+mod world_summary;
 mod worlds;
 
+pub use account_status::*;
+pub use battl_eye_type::*;
+pub use character_auction::*;
+pub use character_info::*;
+pub use character_result::*;
+pub use creature::*;
+pub use death::*;
 pub use game_world_type::*;
+pub use gold::*;
 pub use guild::*;
+pub use guild_details::*;
+pub use highscore::*;
 pub use kill_statistics::*;
 pub use location::*;
+pub use news::*;
+pub use online_record::*;
+pub use online_status::*;
+pub use paginated_response::*;
 pub use player::*;
+pub use players_online_update::*;
 pub use pvp_type::*;
 pub use residence::*;
+pub use response_format::*;
+pub use server_save::*;
+pub use sex::*;
+pub use spell::*;
 pub use transfer_type::*;
 pub use vocation::*;
+pub use world_comparison::*;
 pub use world_details::*;
+pub use world_summary::*;
 pub use worlds::*;
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde::Serialize;
+
+    use super::*;
+
+    /// Asserts that `Display` renders the same name as serde does
+    fn assert_displays_as_serialized<T: Serialize + std::fmt::Display>(values: &[T]) {
+        for value in values {
+            let serialized = serde_json::to_value(value).unwrap();
+            let serialized = match &serialized {
+                serde_json::Value::Object(map) => map["type"].clone(),
+                _ => serialized,
+            };
+
+            assert_eq!(serialized, value.to_string());
+        }
+    }
+
+    #[test]
+    fn enums_display_as_serialized() {
+        assert_displays_as_serialized(&[
+            Location::Europe,
+            Location::SouthAmerica,
+            Location::NorthAmerica,
+            Location::Oceania,
+        ]);
+        assert_displays_as_serialized(&[
+            PvpType::Open,
+            PvpType::Optional,
+            PvpType::Hardcore,
+            PvpType::RetroOpen,
+            PvpType::RetroHardcore,
+        ]);
+        assert_displays_as_serialized(&[GameWorldType::Regular, GameWorldType::Experimental]);
+        assert_displays_as_serialized(&[TransferType::Blocked, TransferType::Locked]);
+        assert_displays_as_serialized(&[
+            Vocation::Knight,
+            Vocation::EliteKnight,
+            Vocation::Sorcerer,
+            Vocation::MasterSorcerer,
+            Vocation::Druid,
+            Vocation::ElderDruid,
+            Vocation::Paladin,
+            Vocation::RoyalPaladin,
+        ]);
+        assert_displays_as_serialized(&[
+            HighscoreCategory::Experience,
+            HighscoreCategory::MagicLevel,
+            HighscoreCategory::Sword,
+            HighscoreCategory::Axe,
+            HighscoreCategory::Club,
+            HighscoreCategory::Distance,
+            HighscoreCategory::Shield,
+            HighscoreCategory::Fist,
+            HighscoreCategory::Fishing,
+            HighscoreCategory::Achievements,
+            HighscoreCategory::LoyaltyPoints,
+        ]);
+        assert_displays_as_serialized(&[ResidenceType::House, ResidenceType::Guildhall]);
+        assert_displays_as_serialized(&[
+            ResidenceStatus::Rented,
+            ResidenceStatus::AuctionNoBid,
+            ResidenceStatus::AuctionWithBid {
+                bid: Gold(1),
+                expiry_time: Utc::now(),
+            },
+            ResidenceStatus::AuctionFinished { bid: Gold(1) },
+        ]);
+    }
+
+    #[test]
+    fn gold_is_serialized_as_a_number() {
+        assert_eq!(
+            serde_json::json!(1555555),
+            serde_json::to_value(Gold(1555555)).unwrap()
+        );
+    }
+
+    #[test]
+    fn gold_is_formatted_like_players_write_it() {
+        assert_eq!("999", Gold(999).to_kk_string());
+        assert_eq!("2.5k", Gold(2500).to_kk_string());
+        assert_eq!("400k", Gold(400000).to_kk_string());
+        assert_eq!("1.55kk", Gold(1555555).to_kk_string());
+        assert_eq!("1.05kk", Gold(1050000).to_kk_string());
+        assert_eq!("1,555,555", Gold(1555555).to_separated_string());
+        assert_eq!("100", Gold(100).to_separated_string());
+        assert_eq!("1,555,555 gold", Gold(1555555).to_string());
+    }
+
+    #[test]
+    fn residence_csv_headers_match_the_serialized_fields() {
+        let residence = Residence {
+            id: 50101,
+            world: "Jaguna".to_string(),
+            town: "Edron".to_string(),
+            residence_type: ResidenceType::House,
+            name: "Castle Shop 1".to_string(),
+            size: 42,
+            rent: Gold(400000),
+            status: ResidenceStatus::Rented,
+        };
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer
+            .serialize(ResidenceCsvRecord::from(&residence))
+            .unwrap();
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+        assert_eq!(
+            ResidenceCsvRecord::HEADERS.join(","),
+            csv.lines().next().unwrap()
+        );
+    }
+}
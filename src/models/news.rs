@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum NewsCategory {
+    Cipsoft,
+    Community,
+    Development,
+    Support,
+    TechnicalIssues,
+}
+
+impl NewsCategory {
+    /// The category as it's named on tibia.com
+    pub fn title(&self) -> &'static str {
+        match self {
+            NewsCategory::Cipsoft => "CipSoft",
+            NewsCategory::Community => "Community",
+            NewsCategory::Development => "Development",
+            NewsCategory::Support => "Support",
+            NewsCategory::TechnicalIssues => "Technical Issues",
+        }
+    }
+}
+
+impl std::str::FromStr for NewsCategory {
+    type Err = anyhow::Error;
+
+    /// Parses the category from the name of its icon, e.g. `newsicon_community_big.png`
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "cipsoft" => Ok(NewsCategory::Cipsoft),
+            "community" => Ok(NewsCategory::Community),
+            "development" => Ok(NewsCategory::Development),
+            "support" => Ok(NewsCategory::Support),
+            "technical" => Ok(NewsCategory::TechnicalIssues),
+            _ => Err(anyhow!("Unexpected news category: '{}'", s)),
+        }
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct News {
+    /// The id of the news article, only linked for articles that don't fit on the page
+    #[schema(example = 7412)]
+    pub id: Option<u32>,
+    #[schema(example = "Summer Update 2023 Teaser")]
+    pub title: String,
+    pub category: NewsCategory,
+    #[schema(value_type = String, format = Date)]
+    pub date: NaiveDate,
+    /// The first paragraph of the article
+    pub teaser: String,
+    /// The article on tibia.com, if linked
+    pub url: Option<String>,
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The record number of players online on a world
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlineRecord {
+    #[schema(example = 1055)]
+    pub count: u32,
+    /// When the record was set
+    #[schema(value_type = String, format = DateTime)]
+    pub date: DateTime<Utc>,
+}
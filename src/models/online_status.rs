@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Whether a character is currently logged in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum OnlineStatus {
+    Online,
+    Offline,
+}
+
+impl OnlineStatus {
+    pub fn is_online(&self) -> bool {
+        *self == OnlineStatus::Online
+    }
+}
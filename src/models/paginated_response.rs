@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{GuildMember, HighscoreEntry, Player};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[aliases(
+    PaginatedPlayers = PaginatedResponse<Player>,
+    PaginatedGuildMembers = PaginatedResponse<GuildMember>,
+    PaginatedHighscores = PaginatedResponse<HighscoreEntry>
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedResponse<T> {
+    /// The items on the requested page
+    pub data: Vec<T>,
+    /// The requested page, starting from 1
+    #[schema(example = 1)]
+    pub page: u32,
+    /// The maximum number of items per page
+    #[schema(example = 50)]
+    pub per_page: u32,
+    /// The total number of items across all pages
+    #[schema(example = 455)]
+    pub total_items: u32,
+    /// The total number of pages
+    #[schema(example = 10)]
+    pub total_pages: u32,
+}
+
+impl<T> PaginatedResponse<T> {
+    /// Picks out the requested page from all items
+    pub fn paginate(items: Vec<T>, page: u32, per_page: u32) -> Self {
+        let total_items = items.len() as u32;
+        let total_pages = total_items.div_ceil(per_page);
+        // pages far past the end would overflow, they are empty either way
+        let offset = (page.saturating_sub(1) as usize).saturating_mul(per_page as usize);
+        let data = items
+            .into_iter()
+            .skip(offset)
+            .take(per_page as usize)
+            .collect();
+
+        Self {
+            data,
+            page,
+            per_page,
+            total_items,
+            total_pages,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A message sent over the players online WebSocket
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayersOnlineUpdate {
+    /// The current number of players online
+    #[schema(example = 152)]
+    pub players_online_count: u32,
+    /// When the number of players online was fetched
+    #[schema(value_type = String, format = DateTime)]
+    pub timestamp: DateTime<Utc>,
+}
@@ -1,8 +1,8 @@
 use anyhow::{anyhow, Result};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Serialize, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum PvpType {
     Open,
@@ -12,6 +12,25 @@ pub enum PvpType {
     RetroHardcore,
 }
 
+impl PvpType {
+    /// The name as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PvpType::Open => "open",
+            PvpType::Optional => "optional",
+            PvpType::Hardcore => "hardcore",
+            PvpType::RetroOpen => "retroOpen",
+            PvpType::RetroHardcore => "retroHardcore",
+        }
+    }
+}
+
+impl std::fmt::Display for PvpType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl std::str::FromStr for PvpType {
     type Err = anyhow::Error;
 
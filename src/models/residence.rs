@@ -1,16 +1,58 @@
+use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use super::Gold;
+
 /// The house type
 #[derive(Serialize, Clone, Copy, Deserialize, Debug, ToSchema)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", try_from = "String")]
 pub enum ResidenceType {
     House,
     Guildhall,
 }
 
+impl ResidenceType {
+    /// The name as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResidenceType::House => "house",
+            ResidenceType::Guildhall => "guildhall",
+        }
+    }
+}
+
+impl std::str::FromStr for ResidenceType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "house" | "houses" => Ok(ResidenceType::House),
+            "guildhall" | "guildhalls" => Ok(ResidenceType::Guildhall),
+            _ => Err(anyhow!(
+                "Invalid residence type '{}', expected one of: house, guildhall",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for ResidenceType {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for ResidenceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// The residence status
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -19,21 +61,60 @@ pub enum ResidenceStatus {
     AuctionNoBid,
     #[serde(rename_all = "camelCase")]
     AuctionWithBid {
-        bid: u32,
+        #[schema(value_type = u32, example = 1555555)]
+        bid: Gold,
         #[schema(value_type = String, format = DateTime)]
         expiry_time: DateTime<Utc>,
     },
     AuctionFinished {
-        bid: u32,
+        #[schema(value_type = u32, example = 1555555)]
+        bid: Gold,
     },
 }
 
+impl ResidenceStatus {
+    /// The status type as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResidenceStatus::Rented => "rented",
+            ResidenceStatus::AuctionNoBid => "auctionNoBid",
+            ResidenceStatus::AuctionWithBid { .. } => "auctionWithBid",
+            ResidenceStatus::AuctionFinished { .. } => "auctionFinished",
+        }
+    }
+
+    /// Whether the residence is being auctioned right now, with or without bids
+    pub fn is_active_auction(&self) -> bool {
+        matches!(
+            self,
+            ResidenceStatus::AuctionNoBid | ResidenceStatus::AuctionWithBid { .. }
+        )
+    }
+
+    /// When the auction ends, only known once someone has bid
+    pub fn expiry_time(&self) -> Option<DateTime<Utc>> {
+        match self {
+            ResidenceStatus::AuctionWithBid { expiry_time, .. } => Some(*expiry_time),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ResidenceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Residence {
     /// The id of the residence (houseid)
     pub id: u32,
+    /// The world the residence is on
+    #[schema(example = "Antica")]
+    pub world: String,
     #[schema(example = "Thais")]
     pub town: String,
     #[serde(rename = "type")]
@@ -45,7 +126,63 @@ pub struct Residence {
     #[schema(example = 16)]
     pub size: u16,
     /// The rent
-    #[schema(example = 50000)]
-    pub rent: u32,
+    #[schema(value_type = u32, example = 50000)]
+    pub rent: Gold,
     pub status: ResidenceStatus,
 }
+
+/// A flattened residence, as CSV can't represent the nested status
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResidenceCsvRecord<'a> {
+    pub id: u32,
+    pub world: &'a str,
+    pub town: &'a str,
+    #[serde(rename = "type")]
+    pub residence_type: ResidenceType,
+    pub name: &'a str,
+    pub size: u16,
+    pub rent: Gold,
+    pub status: &'static str,
+    pub bid: Option<Gold>,
+    pub expiry_time: Option<DateTime<Utc>>,
+}
+
+impl ResidenceCsvRecord<'_> {
+    /// The serialized field names, for the header row of an empty CSV
+    pub const HEADERS: &'static [&'static str] = &[
+        "id",
+        "world",
+        "town",
+        "type",
+        "name",
+        "size",
+        "rent",
+        "status",
+        "bid",
+        "expiryTime",
+    ];
+}
+
+impl<'a> From<&'a Residence> for ResidenceCsvRecord<'a> {
+    fn from(residence: &'a Residence) -> Self {
+        let (bid, expiry_time) = match residence.status {
+            ResidenceStatus::Rented | ResidenceStatus::AuctionNoBid => (None, None),
+            ResidenceStatus::AuctionWithBid { bid, expiry_time } => (Some(bid), Some(expiry_time)),
+            ResidenceStatus::AuctionFinished { bid } => (Some(bid), None),
+        };
+
+        Self {
+            id: residence.id,
+            world: &residence.world,
+            town: &residence.town,
+            residence_type: residence.residence_type,
+            name: &residence.name,
+            size: residence.size,
+            rent: residence.rent,
+            status: residence.status.as_str(),
+            bid,
+            expiry_time,
+        }
+    }
+}
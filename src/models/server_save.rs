@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerSave {
+    /// The time of the next daily server save
+    #[schema(value_type = String, format = DateTime)]
+    pub next_server_save: DateTime<Utc>,
+    /// The number of seconds until the next daily server save
+    #[schema(example = 3600)]
+    pub seconds_until: i64,
+}
@@ -0,0 +1,56 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::Vocation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SpellType {
+    Instant,
+    Rune,
+}
+
+impl std::str::FromStr for SpellType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Instant" => Ok(SpellType::Instant),
+            "Rune" => Ok(SpellType::Rune),
+            _ => Err(anyhow!("Unexpected spell type: '{}'", s)),
+        }
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Spell {
+    #[schema(example = "Light Healing")]
+    pub name: String,
+    /// The words to cast the spell
+    #[schema(example = "exura")]
+    pub formula: String,
+    /// The vocations that can learn the spell
+    pub vocations: Vec<Vocation>,
+    #[serde(rename = "type")]
+    pub spell_type: SpellType,
+    /// The experience level required to learn the spell
+    #[schema(example = 8)]
+    pub level: u16,
+    /// The magic level required to make runes, if any
+    #[schema(example = 4)]
+    pub magic_level: Option<u16>,
+    #[schema(example = 20)]
+    pub mana: Option<u32>,
+    /// The soul points required to make runes, if any
+    #[schema(example = 3)]
+    pub soul: Option<u16>,
+    /// The price in gold to learn the spell
+    #[schema(example = 0)]
+    pub price: u32,
+    /// The cities the spell can be learned in
+    #[schema(example = json!(["Carlin", "Thais"]))]
+    pub cities: Vec<String>,
+}
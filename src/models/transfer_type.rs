@@ -1,14 +1,30 @@
 use anyhow::{anyhow, Result};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum TransferType {
     Blocked,
     Locked,
 }
 
+impl TransferType {
+    /// The name as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferType::Blocked => "blocked",
+            TransferType::Locked => "locked",
+        }
+    }
+}
+
+impl std::fmt::Display for TransferType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl std::str::FromStr for TransferType {
     type Err = anyhow::Error;
 
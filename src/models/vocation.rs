@@ -1,8 +1,8 @@
 use anyhow::{anyhow, Result};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum Vocation {
     Knight,
@@ -15,6 +15,38 @@ pub enum Vocation {
     RoyalPaladin,
 }
 
+impl Vocation {
+    /// The name as it's serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Vocation::Knight => "knight",
+            Vocation::EliteKnight => "eliteKnight",
+            Vocation::Sorcerer => "sorcerer",
+            Vocation::MasterSorcerer => "masterSorcerer",
+            Vocation::Druid => "druid",
+            Vocation::ElderDruid => "elderDruid",
+            Vocation::Paladin => "paladin",
+            Vocation::RoyalPaladin => "royalPaladin",
+        }
+    }
+
+    /// The vocation before promotion, e.g. `Knight` for `EliteKnight`
+    pub fn base(self) -> Self {
+        match self {
+            Vocation::Knight | Vocation::EliteKnight => Vocation::Knight,
+            Vocation::Sorcerer | Vocation::MasterSorcerer => Vocation::Sorcerer,
+            Vocation::Druid | Vocation::ElderDruid => Vocation::Druid,
+            Vocation::Paladin | Vocation::RoyalPaladin => Vocation::Paladin,
+        }
+    }
+}
+
+impl std::fmt::Display for Vocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl std::str::FromStr for Vocation {
     type Err = anyhow::Error;
 
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::WorldDetails;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldComparison {
+    #[serde(flatten)]
+    pub world: WorldDetails,
+    /// Rank among the compared worlds by players online (1 = most players)
+    #[schema(example = 1)]
+    pub players_online_rank: u32,
+    /// Whether players can attack each other on the world
+    #[schema(example = "PvP")]
+    pub pvp_type_category: String,
+    /// Number of days since the world was created
+    #[schema(example = 9686)]
+    pub age_days: u32,
+}
@@ -1,18 +1,21 @@
 use chrono::{DateTime, NaiveDate, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use super::{GameWorldType, Location, Player, PvpType, TransferType};
 
 #[serde_with::skip_serializing_none]
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WorldDetails {
     #[schema(example = "Antica")]
     pub name: String,
+    /// The url of the world on tibia.com
+    #[schema(example = "https://www.tibia.com/community/?subtopic=worlds&world=Antica")]
+    pub tibia_url: String,
     /// If the world is online or not
     pub is_online: bool,
-    /// The current number of players online
+    /// The current number of players online, regardless of how many are included
     #[schema(example = 152)]
     pub players_online_count: u32,
     /// The record number of players online
@@ -38,5 +41,6 @@ pub struct WorldDetails {
     pub transfer_type: Option<TransferType>,
     /// If premium is required to play on this world
     pub premium_required: bool,
+    /// The players online, or the requested slice of them
     pub players_online: Vec<Player>,
 }
@@ -0,0 +1,22 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::WorldDetails;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldSummary {
+    #[serde(flatten)]
+    pub world: WorldDetails,
+    /// The number of guilds on the world, `null` if they could not be fetched
+    #[schema(example = 94)]
+    pub guild_count: Option<u32>,
+    /// The number of players online by vocation, players without a vocation are counted as `none`
+    #[schema(example = json!({ "eliteKnight": 120, "none": 3 }))]
+    pub players_online_by_vocation: BTreeMap<String, u32>,
+    /// The number of houses currently up for auction, `null` if they could not be fetched
+    #[schema(example = 104)]
+    pub active_house_auctions: Option<u32>,
+}
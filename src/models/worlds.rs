@@ -1,13 +1,14 @@
 use chrono::{DateTime, NaiveDate, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::{GameWorldType, Location, PvpType, TransferType};
+use super::{BattlEyeType, GameWorldType, Location, PvpType, TransferType};
 
-#[derive(Serialize, ToSchema)]
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WorldsResponse {
-    /// The current number of players online in all worlds
+    /// The current number of players online in all listed worlds
     #[schema(example = 1234)]
     pub players_online_total: u32,
     /// The record number of players online in all worlds
@@ -17,21 +18,43 @@ pub struct WorldsResponse {
     #[schema(value_type = String, format = DateTime)]
     pub record_date: DateTime<Utc>,
     pub worlds: Vec<World>,
+    /// The change since the previous fetch of the worlds.
+    /// Only included when requested with `includeDelta=true`, and
+    /// `null` for the first fetch.
+    pub delta_since_last_fetch: Option<WorldsDelta>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldsDelta {
+    /// The change in the number of players online in all listed worlds
+    #[schema(example = -12)]
+    pub players_online_total: i64,
+    /// The change in the record number of players online in all worlds
+    #[schema(example = 0)]
+    pub record_players: i64,
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct World {
     #[schema(example = "Antica")]
     pub name: String,
-    /// Current number of players online in this world
+    /// The url of the world on tibia.com
+    #[schema(example = "https://www.tibia.com/community/?subtopic=worlds&world=Antica")]
+    pub tibia_url: String,
+    /// Current number of players online in this world, 0 while it's offline
     #[schema(example = 1337)]
     pub players_online_count: u32,
+    /// If the world is online or not, tibia.com shows "off" instead of a
+    /// player count for offline worlds
+    pub is_online: bool,
     pub location: Location,
     pub pvp_type: PvpType,
     /// Whether the world has battlEye enabled
     pub battl_eye: bool,
+    pub battl_eye_type: BattlEyeType,
     /// The date battlEye was enabled, if it has battlEye
     #[schema(value_type = Option<String>, format = Date)]
     pub battl_eye_date: Option<NaiveDate>,
@@ -40,4 +63,55 @@ pub struct World {
     pub premium_required: bool,
     pub transfer_type: Option<TransferType>,
     pub game_world_type: GameWorldType,
+    /// Whether this is a seasonal tournament world
+    #[schema(example = false)]
+    pub is_tournament_world: bool,
+    /// The record number of players online in this world.
+    /// Only included when requested with `includeRecords=true`.
+    #[schema(example = 1055)]
+    pub players_online_record: Option<u32>,
+    /// The date of the record number of players online in this world.
+    /// Only included when requested with `includeRecords=true`.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub players_online_record_date: Option<DateTime<Utc>>,
+}
+
+/// The order of the listed worlds.
+///
+/// Sorting by creation date is not possible, since it's only shown on the
+/// page of each world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum WorldSort {
+    NameAsc,
+    NameDesc,
+    PlayersOnlineCountAsc,
+    PlayersOnlineCountDesc,
+    /// Grouped by location, alphabetically within each location
+    Location,
+}
+
+impl WorldSort {
+    pub fn sort(&self, worlds: &mut [World]) {
+        match self {
+            WorldSort::NameAsc => worlds.sort_unstable_by(|a, b| a.name.cmp(&b.name)),
+            WorldSort::NameDesc => worlds.sort_unstable_by(|a, b| b.name.cmp(&a.name)),
+            WorldSort::PlayersOnlineCountAsc => worlds.sort_unstable_by(|a, b| {
+                a.players_online_count
+                    .cmp(&b.players_online_count)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+            WorldSort::PlayersOnlineCountDesc => worlds.sort_unstable_by(|a, b| {
+                b.players_online_count
+                    .cmp(&a.players_online_count)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+            WorldSort::Location => worlds.sort_unstable_by(|a, b| {
+                a.location
+                    .as_str()
+                    .cmp(b.location.as_str())
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+        }
+    }
 }
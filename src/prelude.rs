@@ -1,9 +1,45 @@
-use axum::{response::IntoResponse, Json};
+use axum::{http::header, response::IntoResponse, Json};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::Europe::Stockholm;
+use once_cell::sync::Lazy;
 use reqwest::StatusCode;
-use utoipa::{schema, ToSchema};
+use scraper::ElementRef;
+use utoipa::ToSchema;
+
+use crate::selectors;
 
 pub use crate::clients::{Client, TibiaClient, TibiaError, MAINTENANCE_TITLE};
 
+/// The hour of the daily server save, in the time zone of the tibia website
+const SERVER_SAVE_HOUR: u32 = 10;
+/// How many seconds clients should wait before retrying when we're rate limited
+const RATE_LIMITED_RETRY_AFTER: i64 = 30;
+/// How many seconds clients should wait before retrying when tibia.com asks us to slow down,
+/// unless it says how long to wait
+const TOO_MANY_REQUESTS_RETRY_AFTER: u64 = 60;
+
+/// The time of the next daily server save after `now`
+pub fn next_server_save(now: DateTime<Utc>) -> DateTime<Utc> {
+    let today = now.with_timezone(&Stockholm).date_naive();
+    let server_save = |date: chrono::NaiveDate| {
+        date.and_hms_opt(SERVER_SAVE_HOUR, 0, 0)
+            .and_then(|dt| Stockholm.from_local_datetime(&dt).earliest())
+            .expect("Server save to be a valid time")
+    };
+
+    let mut next_server_save = server_save(today);
+    if next_server_save <= now {
+        next_server_save = server_save(today + Duration::days(1));
+    }
+
+    next_server_save.with_timezone(&Utc)
+}
+
+/// The number of seconds until the next daily server save
+pub fn seconds_until_server_save(now: DateTime<Utc>) -> i64 {
+    (next_server_save(now) - now).num_seconds()
+}
+
 pub fn error_chain_fmt(
     e: &impl std::error::Error,
     f: &mut std::fmt::Formatter<'_>,
@@ -27,6 +63,11 @@ pub enum ServerError {
     Unexpected(#[from] anyhow::Error),
     #[error(transparent)]
     Client(#[from] TibiaError),
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    /// The resource exists, but its page is not structured like it used to be
+    #[error("Unexpected page content: {0}")]
+    ScrapeUnexpectedPageContent(String),
 }
 
 #[derive(serde::Serialize, serde::Deserialize, ToSchema)]
@@ -43,6 +84,59 @@ impl PublicErrorBody {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClientErrorCode {
+    ResourceNotFound,
+    InvalidParameter,
+}
+
+impl ClientErrorCode {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ClientErrorCode::ResourceNotFound => StatusCode::NOT_FOUND,
+            ClientErrorCode::InvalidParameter => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// Error body for requests that can't be fulfilled because of the request itself
+#[derive(serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct ClientError {
+    code: ClientErrorCode,
+    #[schema(example = "The requested resource was not found.")]
+    message: String,
+}
+
+impl ClientError {
+    pub fn new(code: ClientErrorCode, message: &str) -> Self {
+        ClientError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found() -> Self {
+        ClientError::new(
+            ClientErrorCode::ResourceNotFound,
+            "The requested resource was not found.",
+        )
+    }
+}
+
+impl IntoResponse for ClientError {
+    fn into_response(self) -> axum::response::Response {
+        (self.code.status(), Json(self)).into_response()
+    }
+}
+
+impl ServerError {
+    /// The error for resources that don't exist, answered with a 404 and [`ClientError::not_found`]
+    pub fn not_found() -> Self {
+        ServerError::Client(TibiaError::NotFound)
+    }
+}
+
 impl std::fmt::Debug for ServerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         error_chain_fmt(self, f)
@@ -59,26 +153,64 @@ impl IntoResponse for ServerError {
                 );
                 (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
             }
-            ServerError::Middleware(_) | ServerError::Unexpected(_) => {
+            ServerError::Middleware(_)
+            | ServerError::Unexpected(_)
+            | ServerError::ScrapeUnexpectedPageContent(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR.into_response()
             }
+            ServerError::InvalidParameter(message) => {
+                ClientError::new(ClientErrorCode::InvalidParameter, &message).into_response()
+            }
             ServerError::Client(e) => match e {
                 TibiaError::Maintenance => {
                     let body = PublicErrorBody::new(
                         "The tibia website failed to process the underlying request",
                     );
-                    (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
+                    let retry_after = seconds_until_server_save(Utc::now());
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        [(header::RETRY_AFTER, retry_after.to_string())],
+                        Json(body),
+                    )
+                        .into_response()
                 }
                 TibiaError::UnsuccessfulRequest(StatusCode::FORBIDDEN) => {
                     let body = PublicErrorBody::new(
                         "Unable to process request due to upstream rate limiting",
                     );
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        [(header::RETRY_AFTER, RATE_LIMITED_RETRY_AFTER.to_string())],
+                        Json(body),
+                    )
+                        .into_response()
+                }
+                TibiaError::ResponseTooLarge(_) => {
+                    let body = PublicErrorBody::new(
+                        "The tibia website responded with an unexpectedly large page",
+                    );
                     (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
                 }
-                TibiaError::NotFound => StatusCode::NOT_FOUND.into_response(),
-                TibiaError::Reqwest(_) | TibiaError::UnsuccessfulRequest(_) => {
-                    StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                TibiaError::NotFound => ClientError::not_found().into_response(),
+                TibiaError::RateLimited(retry_after) => {
+                    let body = PublicErrorBody::new(
+                        "Unable to process request due to upstream rate limiting",
+                    );
+                    let retry_after = retry_after.unwrap_or(TOO_MANY_REQUESTS_RETRY_AFTER);
+                    (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [(header::RETRY_AFTER, retry_after.to_string())],
+                        Json(body),
+                    )
+                        .into_response()
                 }
+                TibiaError::UnsuccessfulRequest(_) => {
+                    let body = PublicErrorBody::new(
+                        "The tibia website failed to process the underlying request",
+                    );
+                    (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
+                }
+                TibiaError::Reqwest(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
             },
         }
     }
@@ -101,3 +233,97 @@ impl Sanitizable for String {
             .replace(' ', " ")
     }
 }
+
+/// The number of bytes of html logged when parsing fails, configurable
+/// through `HTML_SNIPPET_LENGTH` to avoid log spam
+static HTML_SNIPPET_LENGTH: Lazy<usize> = Lazy::new(|| {
+    std::env::var("HTML_SNIPPET_LENGTH")
+        .ok()
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(500)
+});
+
+/// Truncates html to at most `max_length` bytes, without splitting a character
+pub fn truncate_html(html: &str, max_length: usize) -> &str {
+    if html.len() <= max_length {
+        return html;
+    }
+    let mut end = max_length;
+    while !html.is_char_boundary(end) {
+        end -= 1;
+    }
+    &html[..end]
+}
+
+/// Logs the html of an element that couldn't be parsed, so that changes to
+/// the markup of the tibia website can be diagnosed
+pub fn log_unexpected_html(message: &str, element: &ElementRef) {
+    let html = element.html();
+    tracing::error!(
+        html = truncate_html(&html, *HTML_SNIPPET_LENGTH),
+        html_length = html.len(),
+        "{}",
+        message
+    );
+}
+
+/// Finds the `.TableContainer` whose caption matches `caption`
+pub fn find_table<'a>(main_content: &ElementRef<'a>, caption: &str) -> Option<ElementRef<'a>> {
+    main_content
+        .select(&selectors::TABLE_CONTAINER)
+        .find(|container| {
+            container
+                .select(&selectors::TEXT)
+                .next()
+                .map(|c| c.text().collect::<String>().sanitize() == caption)
+                .unwrap_or(false)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_down_to_server_save_later_today() {
+        // 08:00 UTC is 10:00 in Stockholm during summer time
+        let now = Utc.with_ymd_and_hms(2023, 7, 1, 7, 0, 0).unwrap();
+        assert_eq!(3600, seconds_until_server_save(now));
+    }
+
+    #[test]
+    fn counts_down_to_server_save_tomorrow() {
+        // 09:00 UTC is 10:00 in Stockholm during winter time
+        let now = Utc.with_ymd_and_hms(2023, 1, 1, 9, 0, 0).unwrap();
+        assert_eq!(24 * 3600, seconds_until_server_save(now));
+    }
+
+    #[test]
+    fn finds_next_server_save_across_spring_dst_transition() {
+        // 13:00 in Stockholm the day before summer time starts
+        let now = Utc.with_ymd_and_hms(2023, 3, 25, 12, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2023, 3, 26, 8, 0, 0).unwrap(),
+            next_server_save(now)
+        );
+        assert_eq!(20 * 3600, seconds_until_server_save(now));
+    }
+
+    #[test]
+    fn finds_next_server_save_across_autumn_dst_transition() {
+        // 14:00 in Stockholm the day before summer time ends
+        let now = Utc.with_ymd_and_hms(2023, 10, 28, 12, 0, 0).unwrap();
+        assert_eq!(
+            Utc.with_ymd_and_hms(2023, 10, 29, 9, 0, 0).unwrap(),
+            next_server_save(now)
+        );
+        assert_eq!(21 * 3600, seconds_until_server_save(now));
+    }
+
+    #[test]
+    fn truncates_html_on_char_boundaries() {
+        assert_eq!("<td>Ab", truncate_html("<td>Ab</td>", 6));
+        assert_eq!("<td>", truncate_html("<td>ö</td>", 5));
+        assert_eq!("<td></td>", truncate_html("<td></td>", 500));
+    }
+}
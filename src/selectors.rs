@@ -0,0 +1,85 @@
+//! The CSS selectors used to scrape tibia.com, parsed once on first use.
+//! Markup changes on tibia.com should only need to touch this file.
+
+use once_cell::sync::Lazy;
+use scraper::Selector;
+
+macro_rules! selectors {
+    ($($(#[$meta:meta])* $name:ident = $css:literal;)*) => {
+        $(
+            $(#[$meta])*
+            pub static $name: Lazy<Selector> = Lazy::new(|| {
+                Selector::parse($css).expect(concat!("Invalid selector for ", stringify!($name)))
+            });
+        )*
+
+        /// Every selector defined in this module
+        #[cfg(test)]
+        fn all() -> Vec<&'static Lazy<Selector>> {
+            vec![$(&$name),*]
+        }
+    };
+}
+
+selectors! {
+    // Common
+    TITLE = "title";
+    MAIN_CONTENT = ".main-content";
+    TEXT = ".Text";
+    TABLE_CONTAINER = ".TableContainer";
+    TABLE_CONTAINER_CONTENT = ".TableContainer table.TableContent";
+    INNER_TABLE_CONTAINER = ".InnerTableContainer";
+    TABLE_CONTENT = ".TableContent";
+    TABLE_CONTENT_ROW = ".TableContent tr";
+    /// The rows of a table, without its `.LabelH` header
+    TABLE_CONTENT_DATA_ROW = ".TableContent tr:not(.LabelH)";
+    /// The cells of the `.LabelH` header of a table
+    TABLE_CONTENT_HEADER_CELL = ".TableContent tr.LabelH td";
+    ROW = "tr";
+    ROW_WITHOUT_HEADER = "tr:not(:first-child)";
+    /// The alternating rows of a table, leaving out its headers and page navigation
+    STRIPED_ROW = "tr.Odd, tr.Even";
+    /// The cells of the alternating rows in tables without a `.TableContent`
+    STRIPED_ROW_CELL = "tr.Odd > td, tr.Even > td";
+    CELL = "td";
+    LINK = "a";
+    IMG = "img";
+    PARAGRAPH = "p";
+    HELPER_DIV_INDICATOR = ".HelperDivIndicator";
+
+    // Bazaar
+    AUCTION = ".Auction";
+    AUCTION_HEADER = ".AuctionHeader";
+    AUCTION_CHARACTER_NAME = ".AuctionCharacterName a";
+    AUCTION_DATA_LABEL = ".ShortAuctionDataLabel";
+    AUCTION_DATA_VALUE = ".ShortAuctionDataValue";
+
+    // Guilds
+    GUILD_INFORMATION = "#GuildInformationContainer";
+
+    // Houses
+    TOWNS_TABLE = "#houses table.TableContent";
+    TOWN_INPUT = "input[name=town]";
+    HOUSE_ID_INPUT = "input[name=\"houseid\"]";
+
+    // Kill statistics
+    KILL_STATISTICS_CELL = "#KillStatisticsTable tr.DataRow > td";
+
+    // News
+    NEWS_HEADLINE = ".NewsHeadline";
+    NEWS_HEADLINE_ICON = ".NewsHeadlineIcon";
+    NEWS_HEADLINE_DATE = ".NewsHeadlineDate";
+    NEWS_HEADLINE_TEXT = ".NewsHeadlineText";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_selectors_are_valid() {
+        for selector in all() {
+            Lazy::force(selector);
+        }
+    }
+}
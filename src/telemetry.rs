@@ -1,6 +1,18 @@
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    trace::{self, Tracer},
+    Resource,
+};
 use tracing::{subscriber::set_global_default, Subscriber};
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
-use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, EnvFilter, Registry};
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::{
+    fmt::MakeWriter, layer::SubscriberExt, registry::LookupSpan, EnvFilter, Registry,
+};
+
+/// The service name the spans are exported with
+const SERVICE_NAME: &str = "tibia-api";
 
 pub fn get_subscriber<Sink>(
     name: String,
@@ -17,6 +29,7 @@ where
 
     Registry::default()
         .with(env_filter)
+        .with(otlp_layer())
         .with(JsonStorageLayer)
         .with(formatting_layer)
 }
@@ -26,3 +39,44 @@ pub fn init_subscriber(subscriber: impl Subscriber + Send + Sync) {
     tracing_log::LogTracer::init().expect("To init log tracer");
     set_global_default(subscriber).expect("To set global default subscriber");
 }
+
+/// Exports the spans to the OTLP collector at `OTEL_EXPORTER_OTLP_ENDPOINT`, if set.
+///
+/// Viewing the traces requires an OTLP compatible backend, such as Jaeger or Tempo.
+/// Has to be called within a tokio runtime, as the spans are exported in the background.
+fn otlp_layer<S>() -> Option<OpenTelemetryLayer<S, Tracer>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .ok()
+        .filter(|endpoint| !endpoint.is_empty())?;
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(trace::config().with_resource(Resource::new(vec![
+            KeyValue::new("service.name", SERVICE_NAME),
+            KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+        ])))
+        .install_batch(opentelemetry_sdk::runtime::Tokio);
+
+    match tracer {
+        Ok(tracer) => Some(tracing_opentelemetry::layer().with_tracer(tracer)),
+        Err(e) => {
+            // the subscriber isn't installed yet, so this can't be logged
+            eprintln!("Failed to set up the OTLP exporter: {e:?}");
+            None
+        }
+    }
+}
+
+/// Exports the remaining spans, if exporting was set up with `OTEL_EXPORTER_OTLP_ENDPOINT`
+pub async fn shutdown() {
+    // flushing blocks until the spans are exported
+    let _ = tokio::task::spawn_blocking(opentelemetry::global::shutdown_tracer_provider).await;
+}
@@ -0,0 +1,73 @@
+use axum::http::HeaderValue;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// The origins allowed to read the responses of cross-origin requests
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum AllowedOrigins {
+    /// Any origin, without credentials
+    #[default]
+    Any,
+    /// Only these origins, which may include credentials
+    List(Vec<HeaderValue>),
+}
+
+impl AllowedOrigins {
+    /// The comma separated origins of `CORS_ALLOWED_ORIGINS`, any origin when unset or `*`
+    pub fn from_env() -> Self {
+        std::env::var("CORS_ALLOWED_ORIGINS")
+            .map(|origins| Self::parse(&origins))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(origins: &str) -> Self {
+        let origins = origins
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .collect::<Vec<_>>();
+        if origins.is_empty() || origins.contains(&"*") {
+            return AllowedOrigins::Any;
+        }
+
+        let origins = origins
+            .into_iter()
+            .filter_map(|origin| match HeaderValue::from_str(origin) {
+                Ok(origin) => Some(origin),
+                Err(_) => {
+                    tracing::warn!("Ignoring invalid CORS origin '{}'", origin);
+                    None
+                }
+            })
+            .collect();
+
+        AllowedOrigins::List(origins)
+    }
+
+    /// Allows the origins on `cors`, credentials can't be allowed for any origin
+    pub fn apply(self, cors: CorsLayer) -> CorsLayer {
+        match self {
+            AllowedOrigins::Any => cors.allow_origin(Any),
+            AllowedOrigins::List(origins) => cors
+                .allow_origin(AllowOrigin::list(origins))
+                .allow_credentials(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_allowed_origins() {
+        assert_eq!(AllowedOrigins::Any, AllowedOrigins::parse(""));
+        assert_eq!(AllowedOrigins::Any, AllowedOrigins::parse("*"));
+        assert_eq!(
+            AllowedOrigins::List(vec![
+                HeaderValue::from_static("https://example.com"),
+                HeaderValue::from_static("http://localhost:5173"),
+            ]),
+            AllowedOrigins::parse("https://example.com, http://localhost:5173,")
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::Context;
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::prelude::ServerError;
+
+/// Serializes the records as a CSV file download, below the `headers` row,
+/// which is written even when there are no records
+pub fn csv_response<T: Serialize>(
+    headers: &[&str],
+    records: impl IntoIterator<Item = T>,
+    filename: &str,
+) -> Result<Response, ServerError> {
+    let body = to_csv(headers, records)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+fn to_csv<T: Serialize>(
+    headers: &[&str],
+    records: impl IntoIterator<Item = T>,
+) -> anyhow::Result<Vec<u8>> {
+    // the header row is written by hand, serde only knows it once there is a record
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(vec![]);
+    writer
+        .write_record(headers)
+        .context("Failed to write CSV header")?;
+    for record in records {
+        writer
+            .serialize(record)
+            .context("Failed to serialize CSV record")?;
+    }
+
+    writer.into_inner().context("Failed to write CSV")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Record {
+        name: &'static str,
+        level: u32,
+    }
+
+    #[test]
+    fn writes_the_header_without_records() {
+        let csv = to_csv(&["name", "level"], Vec::<Record>::new()).unwrap();
+
+        assert_eq!("name,level\n", String::from_utf8(csv).unwrap());
+    }
+
+    #[test]
+    fn writes_the_header_above_the_records() {
+        let records = [Record {
+            name: "Urinchoklad",
+            level: 8,
+        }];
+
+        let csv = to_csv(&["name", "level"], records).unwrap();
+
+        assert_eq!(
+            "name,level\nUrinchoklad,8\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+}
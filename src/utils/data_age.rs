@@ -0,0 +1,110 @@
+use std::{
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use axum::{
+    http::{HeaderName, HeaderValue, Request},
+    response::{IntoResponseParts, Response, ResponseParts},
+};
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+
+/// The header telling how many seconds ago the data was fetched from tibia.com
+pub const DATA_AGE_HEADER: &str = "x-tibia-data-age";
+
+/// When the data of a response was fetched from tibia.com.
+///
+/// Handlers return it as part of their response, and [`DataAgeLayer`] turns it
+/// into the `X-Tibia-Data-Age` header.
+#[derive(Debug, Clone, Copy)]
+pub struct DataAge {
+    fetched_at: Instant,
+}
+
+impl DataAge {
+    /// Data that was just fetched
+    pub fn now() -> Self {
+        Self::fetched_at(Instant::now())
+    }
+
+    pub fn fetched_at(fetched_at: Instant) -> Self {
+        Self { fetched_at }
+    }
+
+    /// When the page of a response from the client was fetched, which is
+    /// earlier than now for pages that were cached
+    pub fn of(response: &reqwest::Response) -> Self {
+        response
+            .extensions()
+            .get::<DataAge>()
+            .copied()
+            .unwrap_or_else(DataAge::now)
+    }
+
+    /// The age of data combined from several pages, which is that of the oldest page
+    pub fn oldest(self, other: DataAge) -> Self {
+        Self::fetched_at(self.fetched_at.min(other.fetched_at))
+    }
+
+    /// The number of whole seconds since the data was fetched
+    pub fn seconds(&self) -> u64 {
+        self.fetched_at.elapsed().as_secs()
+    }
+}
+
+impl IntoResponseParts for DataAge {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        res.extensions_mut().insert(self);
+        Ok(res)
+    }
+}
+
+/// Adds the `X-Tibia-Data-Age` header to responses that have a [`DataAge`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DataAgeLayer;
+
+impl<S> Layer<S> for DataAgeLayer {
+    type Service = DataAgeService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DataAgeService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DataAgeService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for DataAgeService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            // handlers that don't touch tibia.com don't set an age
+            if let Some(data_age) = response.extensions().get::<DataAge>() {
+                let seconds = HeaderValue::from(data_age.seconds());
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(DATA_AGE_HEADER), seconds);
+            }
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::de::DeserializeOwned;
+
+use crate::prelude::ServerError;
+
+/// Like `axum::extract::Query`, but rejects malformed query strings with a
+/// `400 Bad Request` describing which parameter is invalid
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ServerError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let deserializer =
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+
+        serde_path_to_error::deserialize(deserializer)
+            .map(Query)
+            .map_err(|e| {
+                let parameter = e.path().to_string();
+                // serde quotes names with backticks, e.g.
+                // "unknown variant `x`, expected one of `a`, `b`"
+                let reason = e.inner().to_string();
+                let reason = match reason.split_once(", expected") {
+                    Some((_, expected)) => format!("expected{expected}"),
+                    None => reason,
+                }
+                .replace('`', "");
+
+                let message = match parameter.as_str() {
+                    "." => format!("Invalid query parameters: {reason}"),
+                    _ => format!("Invalid value for parameter '{parameter}': {reason}"),
+                };
+                ServerError::InvalidParameter(message)
+            })
+    }
+}
@@ -1 +1,12 @@
+pub mod cors;
+pub mod csv;
+pub mod data_age;
+pub mod extract;
 pub mod openapi;
+pub mod parsing;
+pub mod request_id;
+pub mod result_cache;
+pub mod trace;
+
+/// The header telling how many items there are in total, regardless of `limit` and `offset`
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
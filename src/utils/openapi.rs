@@ -1,41 +1,110 @@
+use std::collections::BTreeMap;
+
 use crate::handlers;
 use crate::models::*;
 use crate::prelude::*;
-use utoipa::openapi::{self, InfoBuilder};
+use utoipa::openapi::{self, InfoBuilder, Server};
 use utoipa::OpenApi;
 
-pub fn create_openapi_docs() -> openapi::OpenApi {
+/// The version of the docs served at `/openapi.json`
+pub const LATEST_VERSION: &str = "v1";
+
+/// The openapi docs of each version of the api, e.g. `v1`
+pub type VersionedOpenApiDocs = BTreeMap<&'static str, openapi::OpenApi>;
+
+/// The public URL the API is reachable at, used for the OpenAPI servers list
+pub fn public_base_url() -> String {
+    std::env::var("PUBLIC_BASE_URL").unwrap_or("http://localhost:3000".to_string())
+}
+
+pub fn create_versioned_openapi_docs(public_base_url: &str) -> VersionedOpenApiDocs {
+    BTreeMap::from([("v1", create_openapi_docs(public_base_url))])
+}
+
+pub fn create_openapi_docs(public_base_url: &str) -> openapi::OpenApi {
     #[derive(OpenApi)]
     #[openapi(
-        servers(
-            (url = "https://tibia.ankarhem.dev"),
-        ),
         paths(
             handlers::towns::get,
             handlers::worlds::get,
+            handlers::worlds_compare::get,
             handlers::worlds_world_name::get,
             handlers::worlds_world_name_guilds::get,
+            handlers::worlds_world_name_guilds_guild_name::get,
+            handlers::worlds_world_name_guilds_guild_name_members::get,
+            handlers::worlds_world_name_highscores_category::get,
             handlers::worlds_world_name_kill_statistics::get,
+            handlers::worlds_world_name_kill_statistics_race::get,
+            handlers::worlds_world_name_online_players::get,
+            handlers::worlds_world_name_players_online_record::get,
+            handlers::worlds_world_name_quest_titles::get,
             handlers::worlds_world_name_residences::get,
+            handlers::worlds_world_name_summary::get,
+            handlers::characters::post,
+            handlers::characters_character_name::get,
+            handlers::characters_character_name_deaths::get,
+            handlers::creatures_creature_name::get,
+            handlers::guilds_guild_name::get,
+            handlers::news_rss::get,
+            handlers::residences_auctions::get,
+            handlers::server_save::get,
+            handlers::spells::get,
+            handlers::bazaar::get,
         ),
         components(schemas(
             PublicErrorBody,
+            ClientError,
+            ClientErrorCode,
             WorldDetails,
+            WorldComparison,
+            WorldSummary,
             WorldsResponse,
+            WorldSort,
+            WorldsDelta,
+            OnlineRecord,
+            OnlineStatus,
             GameWorldType,
+            BattlEyeType,
             Location,
             Player,
+            PaginatedPlayers,
             Vocation,
             PvpType,
             TransferType,
             World,
             Guild,
+            PaginatedGuildDetails,
+            PaginatedGuildMembers,
+            GuildMember,
+            GuildWar,
+            GuildWarStatus,
+            HighscoreCategory,
+            HighscoreEntry,
+            PaginatedHighscores,
             KillStatistics,
+            KillStatSort,
             KilledAmounts,
             RaceKillStatistics,
+            RaceKillStatisticsWithTotals,
             Residence,
             ResidenceType,
             ResidenceStatus,
+            CharacterInfo,
+            CharacterHouse,
+            CharacterOutfit,
+            CharacterGuild,
+            AccountStatus,
+            Sex,
+            CharacterResult,
+            CharacterLookupError,
+            handlers::characters::CharactersBody,
+            Death,
+            DeathKiller,
+            Creature,
+            Spell,
+            ServerSave,
+            SpellType,
+            CharacterAuction,
         )),
         tags()
     )]
@@ -46,6 +115,7 @@ pub fn create_openapi_docs() -> openapi::OpenApi {
         .description(Some(API_DESCRIPTION))
         .version("1.0.0")
         .build();
+    openapi.servers = Some(vec![Server::new(public_base_url)]);
 
     openapi
 }
@@ -63,9 +133,37 @@ The source code is available on [GitHub](https://github.com/ankarhem/tibia-api-r
 
 Contact me at [jakob@ankarhem.dev](mailto:jakob@ankarhem.dev), or raise an [issue](https://github.com/ankarhem/tibia-api-rust/issues).
 
+Responses with data from tibia.com include an `X-Tibia-Data-Age` header, telling how many seconds ago the data was fetched.
+
+<h2>Migration notes</h2>
+
+- Characters have an `accountStatus` of `free` or `premium` instead of `hasPremium`. Replace `hasPremium` with `accountStatus == "premium"`.
+- The `spawnPoint` of characters is now called `residence`, like the row on tibia.com.
+- `GET /api/v1/worlds` no longer lists offline worlds by default. Pass `includeOffline=true` to get them back, each world now has an `isOnline` flag.
+
 <h2>Disclaimer</h2>
 
 The data is based on [tibia.com](https://www.tibia.com/), the only official Tibia website.
 
 Tibia is a registered trademark of [CipSoft GmbH](https://www.cipsoft.com/en/). Tibia and all products related to Tibia are copyright by [CipSoft GmbH](https://www.cipsoft.com/en/).
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_public_base_url_as_server() {
+        let openapi = create_openapi_docs("https://example.com");
+        let servers = openapi.servers.expect("Servers to be set");
+
+        assert_eq!("https://example.com", servers[0].url);
+    }
+
+    #[test]
+    fn has_docs_for_the_latest_version() {
+        let docs = create_versioned_openapi_docs("https://example.com");
+
+        assert!(docs.contains_key(LATEST_VERSION));
+    }
+}
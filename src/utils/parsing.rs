@@ -0,0 +1,261 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static GOLD_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([\d,]+) gold").expect("Invalid gold regex"));
+static TIME_LEFT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(\d+) (days?|hours?|minutes?) left").expect("Invalid time left regex")
+});
+static LESS_THAN_AN_HOUR_LEFT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"less than (?:an|1) hour left").expect("Invalid less than an hour regex")
+});
+
+/// Parses an amount of gold such as `1,555,555 gold`
+pub fn parse_gold(text: &str) -> Result<u32> {
+    let gold_str = GOLD_RE
+        .captures(text)
+        .and_then(|m| m.get(1))
+        .map(|g| g.as_str().replace(',', ""))
+        .context(format!("Expected gold in `{}`", text))?;
+
+    gold_str
+        .parse::<u32>()
+        .context(format!("Failed to parse gold `{:?}`", gold_str))
+}
+
+/// Parses the time left such as `2 days left`, `5 hours left`, `30 minutes left`
+/// or `less than an hour left` into the point in time it runs out, relative to `now`
+pub fn parse_time_left(text: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    // without a number the auction ends within the hour, so assume the end of it
+    let (time, time_unit) = if LESS_THAN_AN_HOUR_LEFT_RE.is_match(text) {
+        (0, "hours")
+    } else {
+        let time_matches = TIME_LEFT_RE
+            .captures(text)
+            .context(format!("Time not found: `{}`", text))?;
+
+        let time: i64 = time_matches
+            .get(1)
+            .map(|t| t.as_str())
+            .and_then(|t| t.parse().ok())
+            .context("Could not parse time")?;
+        let time_unit = time_matches
+            .get(2)
+            .map(|u| u.as_str())
+            .context("Could not parse time unit")?;
+
+        (time, time_unit)
+    };
+
+    let current_minute = now
+        .with_second(0)
+        .and_then(|d| d.with_nanosecond(0))
+        .context("Failed to construct current time")?;
+    let current_dt = current_minute
+        .with_minute(0)
+        .context("Failed to construct current time")?;
+
+    // if unit is days, set hour to 8 (utc server save)
+    // otherwise we need to add an hour (0h30min left => set min 0 and add hour)
+    // and likewise a minute for minutes (0min30s left => set sec 0 and add minute)
+    let (current_dt, duration) = match time_unit {
+        "day" | "days" => (
+            current_dt.with_hour(8).context("Failed to set hour to 8")?,
+            Duration::days(time),
+        ),
+        "minute" | "minutes" => (
+            current_minute + Duration::minutes(1),
+            Duration::minutes(time),
+        ),
+        _ => (current_dt + Duration::hours(1), Duration::hours(time)),
+    };
+
+    current_dt.checked_add_signed(duration).context(format!(
+        "Failed to calculate expiry time `{time}` with unit `{time_unit}`"
+    ))
+}
+
+/// Parses a month such as `October 2020`, or the older `10/20`, into its first day
+pub fn parse_month(text: &str) -> Result<NaiveDate> {
+    let date = format!("01 {text}");
+
+    NaiveDate::parse_from_str(&date, "%d %B %Y")
+        .or_else(|_| NaiveDate::parse_from_str(&date, "%d %m/%y"))
+        .context(format!("Failed to parse month `{}`", text))
+}
+
+/// Capitalizes every word, including those following an apostrophe, so that
+/// `port hope` becomes `Port Hope` and `ab'dendriel` becomes `Ab'Dendriel`
+pub fn to_title_case(text: &str) -> String {
+    let mut title = String::with_capacity(text.len());
+    let mut capitalize_next = true;
+    for c in text.chars() {
+        if capitalize_next {
+            title.extend(c.to_uppercase());
+        } else {
+            title.extend(c.to_lowercase());
+        }
+        capitalize_next = c == ' ' || c == '\'';
+    }
+
+    title
+}
+
+/// Splits a comma separated list of names, ignoring commas within quotes and
+/// dropping the empty names left by stray commas
+pub fn parse_names(text: &str) -> Vec<String> {
+    let mut names = vec![];
+    let mut name = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => names.push(std::mem::take(&mut name)),
+            c => name.push(c),
+        }
+    }
+    names.push(name);
+
+    names
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Turns the `src` or `href` of an element into an absolute url, tibia.com
+/// links some of its images relative to `https://www.tibia.com`
+pub fn absolute_url(src: &str) -> String {
+    if src.starts_with("https://") || src.starts_with("http://") {
+        src.to_string()
+    } else if let Some(src) = src.strip_prefix("//") {
+        format!("https://{src}")
+    } else {
+        format!("https://www.tibia.com/{}", src.trim_start_matches('/'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2023, 9, 16, 14, 30, 12).unwrap()
+    }
+
+    #[test]
+    fn parses_gold() {
+        assert_eq!(1555555, parse_gold("1555555 gold; 1 day left").unwrap());
+        assert_eq!(2500, parse_gold("2,500 gold").unwrap());
+        assert!(parse_gold("auctioned (no bid yet)").is_err());
+    }
+
+    #[test]
+    fn parses_names() {
+        assert_eq!(vec!["Urinchoklad Jr"], parse_names("Urinchoklad Jr "));
+        assert_eq!(
+            vec!["Urinchoklad Jr", "Old Urin", "Young Urin"],
+            parse_names("Urinchoklad Jr, Old Urin,Young Urin,")
+        );
+        assert_eq!(
+            vec!["Urin, Jr", "Old Urin"],
+            parse_names(r#""Urin, Jr", Old Urin"#)
+        );
+        assert!(parse_names(" ").is_empty());
+    }
+
+    #[test]
+    fn makes_urls_absolute() {
+        assert_eq!(
+            "https://www.tibia.com/community/img/guilds/12345.gif",
+            absolute_url("/community/img/guilds/12345.gif")
+        );
+        assert_eq!(
+            "https://static.tibia.com/images/guildlogos/Red_Rose.gif",
+            absolute_url("https://static.tibia.com/images/guildlogos/Red_Rose.gif")
+        );
+        assert_eq!(
+            "https://static.tibia.com/images/guildlogos/Red_Rose.gif",
+            absolute_url("//static.tibia.com/images/guildlogos/Red_Rose.gif")
+        );
+    }
+
+    #[test]
+    fn parses_days_left_until_server_save() {
+        assert_eq!(
+            Utc.with_ymd_and_hms(2023, 9, 17, 8, 0, 0).unwrap(),
+            parse_time_left("1 day left", now()).unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_hours_left_rounded_up_to_the_hour() {
+        assert_eq!(
+            Utc.with_ymd_and_hms(2023, 9, 17, 14, 0, 0).unwrap(),
+            parse_time_left("23 hours left", now()).unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_minutes_left_rounded_up_to_the_minute() {
+        assert_eq!(
+            Utc.with_ymd_and_hms(2023, 9, 16, 15, 1, 0).unwrap(),
+            parse_time_left("1555555 gold; 30 minutes left", now()).unwrap()
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2023, 9, 16, 14, 32, 0).unwrap(),
+            parse_time_left("1 minute left", now()).unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_less_than_an_hour_left_as_the_end_of_the_hour() {
+        let end_of_hour = Utc.with_ymd_and_hms(2023, 9, 16, 15, 0, 0).unwrap();
+
+        assert_eq!(
+            end_of_hour,
+            parse_time_left("1555555 gold; less than an hour left", now()).unwrap()
+        );
+        assert_eq!(
+            end_of_hour,
+            parse_time_left("less than 1 hour left", now()).unwrap()
+        );
+    }
+
+    #[test]
+    fn fails_on_finished_auctions() {
+        assert!(parse_time_left("1555555 gold; finished", now()).is_err());
+    }
+
+    #[test]
+    fn parses_months_to_their_first_day() {
+        let first_of_october = NaiveDate::from_ymd_opt(2020, 10, 1).unwrap();
+
+        assert_eq!(first_of_october, parse_month("October 2020").unwrap());
+        assert_eq!(first_of_october, parse_month("10/20").unwrap());
+        assert!(parse_month("2020").is_err());
+    }
+
+    #[test]
+    fn title_cases_multi_word_towns() {
+        for town in ["Gray Beach", "Liberty Bay", "Port Hope"] {
+            assert_eq!(town, to_title_case(&town.to_lowercase()));
+            assert_eq!(town, to_title_case(&town.to_uppercase()));
+        }
+    }
+
+    #[test]
+    fn title_cases_after_apostrophes() {
+        assert_eq!("Ab'Dendriel", to_title_case("ab'dendriel"));
+    }
+
+    #[test]
+    fn title_case_is_idempotent() {
+        let once = to_title_case("liBERTY bay");
+        assert_eq!(once, to_title_case(&once));
+    }
+}
@@ -0,0 +1,86 @@
+use std::task::{Context, Poll};
+
+use axum::http::{HeaderName, HeaderValue, Request, Response};
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+use tower_request_id::RequestId;
+
+/// The header holding the id of the request, to correlate responses with the logs
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Inbound request ids longer than this are replaced with a generated one
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Echoes the request id back in the `X-Request-Id` header.
+///
+/// An inbound `X-Request-Id` is reused if present, otherwise the id generated
+/// by the `RequestIdLayer` is used. Either way the request carries the header
+/// afterwards, so that inner layers can log it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdHeaderLayer;
+
+impl<S> Layer<S> for RequestIdHeaderLayer {
+    type Service = RequestIdHeaderService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdHeaderService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestIdHeaderService<S> {
+    inner: S,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for RequestIdHeaderService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let header_name = HeaderName::from_static(REQUEST_ID_HEADER);
+        let inbound = request
+            .headers()
+            .get(&header_name)
+            .filter(|value| is_valid_request_id(value))
+            .cloned();
+        let generated = request
+            .extensions()
+            .get::<RequestId>()
+            .and_then(|id| HeaderValue::from_str(&id.to_string()).ok());
+
+        let request_id = inbound.or(generated);
+        match &request_id {
+            Some(request_id) => {
+                request
+                    .headers_mut()
+                    .insert(header_name.clone(), request_id.clone());
+            }
+            None => {
+                request.headers_mut().remove(&header_name);
+            }
+        }
+
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            if let Some(request_id) = request_id {
+                response.headers_mut().insert(header_name, request_id);
+            }
+            Ok(response)
+        })
+    }
+}
+
+fn is_valid_request_id(value: &HeaderValue) -> bool {
+    !value.is_empty() && value.len() <= MAX_REQUEST_ID_LENGTH && value.to_str().is_ok()
+}
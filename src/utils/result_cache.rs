@@ -0,0 +1,172 @@
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use moka::{future::Cache, Expiry};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::data_age::DataAge;
+use crate::prelude::{next_server_save, ServerError};
+
+/// The number of results kept, the least recently used results are evicted first
+const MAX_CAPACITY: u64 = 10_000;
+
+/// A parsed result, kept as json so that any model can be cached
+#[derive(Clone)]
+struct CachedResult {
+    json: Arc<serde_json::Value>,
+    data_age: DataAge,
+    time_to_live: Duration,
+}
+
+/// Expires every result after its own time to live
+struct ResultExpiry;
+
+impl Expiry<String, CachedResult> for ResultExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        result: &CachedResult,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(result.time_to_live)
+    }
+}
+
+/// Keeps the parsed results of handlers in memory, so that repeated requests
+/// skip both fetching and parsing the page of tibia.com.
+///
+/// Results are keyed by a description of the request, such as `world:antica`,
+/// and never outlive the next server save.
+#[derive(Clone)]
+pub struct ResultCache {
+    cache: Cache<String, CachedResult>,
+}
+
+impl std::fmt::Debug for ResultCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResultCache")
+            .field("entries", &self.cache.entry_count())
+            .finish()
+    }
+}
+
+impl ResultCache {
+    pub fn new() -> Self {
+        let cache = Cache::builder()
+            .max_capacity(MAX_CAPACITY)
+            .expire_after(ResultExpiry)
+            .build();
+
+        Self { cache }
+    }
+
+    /// Returns the cached result for `key`, or awaits `fetch` and caches its
+    /// result for `time_to_live`, along with the age of the pages it was parsed from.
+    /// Errors are left uncached.
+    ///
+    /// With `bypass` the cached result is ignored, and replaced by the fetched one.
+    pub async fn get_or_fetch<T, F>(
+        &self,
+        key: String,
+        time_to_live: Duration,
+        bypass: bool,
+        fetch: F,
+    ) -> Result<(DataAge, T), ServerError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<(DataAge, T), ServerError>>,
+    {
+        if !bypass {
+            if let Some(cached) = self.cache.get(&key).await {
+                match T::deserialize(&*cached.json) {
+                    Ok(result) => return Ok((cached.data_age, result)),
+                    Err(e) => tracing::warn!("Failed to read cached result {}: {:?}", key, e),
+                }
+            }
+        }
+
+        let (data_age, result) = fetch.await?;
+        match serde_json::to_value(&result) {
+            Ok(json) => {
+                let cached = CachedResult {
+                    json: Arc::new(json),
+                    data_age,
+                    time_to_live: time_to_live.min(until_server_save()),
+                };
+                self.cache.insert(key, cached).await;
+            }
+            Err(e) => tracing::warn!("Failed to cache result {}: {:?}", key, e),
+        }
+
+        Ok((data_age, result))
+    }
+}
+
+impl Default for ResultCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The time left until the next server save, when most data on tibia.com changes
+fn until_server_save() -> Duration {
+    let now = Utc::now();
+    (next_server_save(now) - now).to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_cached_results() {
+        let cache = ResultCache::new();
+        let ttl = Duration::from_secs(60);
+
+        let (_, first) = cache
+            .get_or_fetch("key".to_string(), ttl, false, async {
+                Ok((DataAge::now(), 1))
+            })
+            .await
+            .unwrap();
+        let (_, second) = cache
+            .get_or_fetch("key".to_string(), ttl, false, async {
+                Ok((DataAge::now(), 2))
+            })
+            .await
+            .unwrap();
+        let (_, bypassed) = cache
+            .get_or_fetch("key".to_string(), ttl, true, async {
+                Ok((DataAge::now(), 3))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!((1, 1, 3), (first, second, bypassed));
+    }
+
+    #[tokio::test]
+    async fn leaves_errors_uncached() {
+        let cache = ResultCache::new();
+        let ttl = Duration::from_secs(60);
+
+        let error = cache
+            .get_or_fetch::<u32, _>("key".to_string(), ttl, false, async {
+                Err(ServerError::InvalidParameter("invalid".to_string()))
+            })
+            .await;
+        let (_, result) = cache
+            .get_or_fetch("key".to_string(), ttl, false, async {
+                Ok((DataAge::now(), 2))
+            })
+            .await
+            .unwrap();
+
+        assert!(error.is_err());
+        assert_eq!(2, result);
+    }
+}
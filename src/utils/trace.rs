@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, Response},
+};
+use tracing::{Level, Span};
+
+use super::request_id::REQUEST_ID_HEADER;
+
+/// Routes polled often enough that tracing them at INFO only adds noise
+const QUIET_ROUTES: &[&str] = &["/__healthcheck"];
+
+/// Creates the span of a request, at DEBUG for the quiet routes and INFO otherwise
+pub fn make_span(request: &Request<Body>) -> Span {
+    // We get the request id from the header set by the `RequestIdHeaderLayer`
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|id| id.to_str().ok())
+        .unwrap_or("unknown");
+
+    // And then we put it along with other information into the `request` span
+    if QUIET_ROUTES.contains(&request.uri().path()) {
+        tracing::debug_span!(
+            "request",
+            id = %request_id,
+            method = %request.method(),
+            uri = %request.uri(),
+        )
+    } else {
+        tracing::info_span!(
+            "request",
+            id = %request_id,
+            method = %request.method(),
+            uri = %request.uri(),
+        )
+    }
+}
+
+/// Logs the response at the same level as the span of its request
+pub fn on_response<B>(response: &Response<B>, latency: Duration, span: &Span) {
+    let status = response.status().as_u16();
+    let latency = format!("{} ms", latency.as_millis());
+
+    match span.metadata().map(|metadata| *metadata.level()) {
+        Some(Level::INFO) => {
+            tracing::info!(%latency, status, "finished processing request")
+        }
+        Some(_) => tracing::debug!(%latency, status, "finished processing request"),
+        // the span is disabled when its level is filtered out, so is the response
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span_for(uri: &str) -> Span {
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(Level::INFO)
+            .with_writer(std::io::sink)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            make_span(&request)
+        })
+    }
+
+    #[test]
+    fn healthcheck_requests_are_not_traced_at_info() {
+        assert!(span_for("/__healthcheck").is_disabled());
+    }
+
+    #[test]
+    fn data_requests_are_traced_at_info() {
+        let span = span_for("/api/v1/worlds");
+
+        assert!(!span.is_disabled());
+        assert_eq!(Some(&Level::INFO), span.metadata().map(|m| m.level()));
+    }
+}
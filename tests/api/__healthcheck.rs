@@ -1,6 +1,10 @@
 use super::*;
+use std::time::Duration;
+
 use pretty_assertions::assert_eq;
 use reqwest::StatusCode;
+use serde_json::Value;
+use tibia_api::warm_cache;
 
 #[tokio::test]
 async fn healthcheck_works() {
@@ -12,3 +16,171 @@ async fn healthcheck_works() {
 
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn omits_data_age_header() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::get(format!("http://{addr}/__healthcheck"))
+        .await
+        .unwrap();
+
+    assert_eq!(None, response.headers().get("x-tibia-data-age"));
+}
+
+#[tokio::test]
+async fn reports_cache_not_warmed_up_by_default() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::get(format!("http://{addr}/__healthcheck"))
+        .await
+        .unwrap();
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(false, received_json["cacheWarmedUp"]);
+    assert_eq!(Value::Null, received_json["lastSuccessfulFetch"]);
+}
+
+#[tokio::test]
+async fn reports_cache_warmed_up() {
+    let client = MockedClient::new()
+        .page_body(
+            "fetch_worlds_page",
+            include_str!("../mocks/worlds-battleye-200.html"),
+        )
+        .page_body(
+            "fetch_world_details_page",
+            include_str!("../mocks/world-antica-200.html"),
+        );
+    let state = AppState::with_client(client);
+    tokio::spawn(warm_cache(state.clone()));
+    let addr = spawn_app(state);
+
+    let mut cache_warmed_up = Value::Null;
+    for _ in 0..50 {
+        let response = reqwest::get(format!("http://{addr}/__healthcheck"))
+            .await
+            .unwrap();
+        cache_warmed_up = response.json::<Value>().await.unwrap()["cacheWarmedUp"].clone();
+        if cache_warmed_up == true {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    assert_eq!(true, cache_warmed_up);
+
+    let response = reqwest::get(format!("http://{addr}/__healthcheck"))
+        .await
+        .unwrap();
+    let received_json = response.json::<Value>().await.unwrap();
+    assert!(received_json["lastSuccessfulFetch"].is_string());
+}
+
+#[tokio::test]
+async fn warm_up_caches_the_world_details() {
+    let client = MockedClient::new()
+        .page_body(
+            "fetch_worlds_page",
+            include_str!("../mocks/worlds-battleye-200.html"),
+        )
+        .page_body(
+            "fetch_world_details_page",
+            include_str!("../mocks/world-antica-200.html"),
+        );
+    let state = AppState::with_client(client.clone());
+    tokio::spawn(warm_cache(state.clone()));
+    let addr = spawn_app(state);
+
+    for _ in 0..50 {
+        let response = reqwest::get(format!("http://{addr}/__healthcheck"))
+            .await
+            .unwrap();
+        if response.json::<Value>().await.unwrap()["cacheWarmedUp"] == true {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let fetches = client.fetches("fetch_world_details_page");
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Antica"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(fetches, client.fetches("fetch_world_details_page"));
+}
+
+#[tokio::test]
+async fn shallow_healthcheck_does_not_reach_tibia() {
+    let client = MockedClient::new().status(StatusCode::INTERNAL_SERVER_ERROR);
+    let state = AppState::with_client(client.clone());
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/__healthcheck?deep=false"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("ok", received_json["status"]);
+    assert_eq!(Value::Null, received_json["tibiaReachable"]);
+    assert_eq!(0, client.fetches("fetch_worlds_page"));
+}
+
+#[tokio::test]
+async fn deep_healthcheck_reports_tibia_reachable() {
+    let client = MockedClient::new().body(include_str!("../mocks/worlds-200.html"));
+    let addr = spawn_app(AppState::with_client(client));
+
+    let response = reqwest::get(format!("http://{addr}/__healthcheck?deep=true"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("ok", received_json["status"]);
+    assert_eq!(true, received_json["tibiaReachable"]);
+}
+
+#[tokio::test]
+async fn deep_healthcheck_reports_tibia_unreachable() {
+    let unreachable = MockedClient::new().status(StatusCode::BAD_GATEWAY);
+    let maintenance = MockedClient::new().body(include_str!("../mocks/maintenance-200.html"));
+
+    for client in [unreachable, maintenance] {
+        let addr = spawn_app(AppState::with_client(client));
+
+        let response = reqwest::get(format!("http://{addr}/__healthcheck?deep=true"))
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!("degraded", received_json["status"]);
+        assert_eq!(false, received_json["tibiaReachable"]);
+    }
+}
+
+#[tokio::test]
+async fn deep_healthcheck_notices_tibia_going_down() {
+    let client = MockedClient::new().body(include_str!("../mocks/worlds-200.html"));
+    let addr = spawn_app(AppState::with_client(client.clone()));
+
+    let response = reqwest::get(format!("http://{addr}/__healthcheck?deep=true"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    client.set_status(StatusCode::BAD_GATEWAY);
+    let response = reqwest::get(format!("http://{addr}/__healthcheck?deep=true"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(false, received_json["tibiaReachable"]);
+    // the cached worlds page must not be used for the probe
+    assert_eq!(2, client.fetches("probe_worlds_page"));
+    assert_eq!(0, client.fetches("fetch_worlds_page"));
+}
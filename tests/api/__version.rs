@@ -0,0 +1,20 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+async fn returns_the_version() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::get(format!("http://{addr}/__version"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert!(!received_json["version"].as_str().unwrap().is_empty());
+    assert!(!received_json["gitSha"].as_str().unwrap().is_empty());
+    assert!(received_json["builtAt"].is_string());
+}
@@ -0,0 +1,109 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn can_get_auctions() {
+    let body = include_str!("../mocks/bazaar-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/bazaar"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+
+    assert_eq!(
+        json!([
+            {
+                "id": 1234567,
+                "name": "Knight Of Antica",
+                "level": 312,
+                "vocation": "eliteKnight",
+                "sex": "male",
+                "world": "Antica",
+                "bid": 2500,
+                "hasBid": true,
+                "startedAt": "2023-10-12T08:00:00Z",
+                "endsAt": "2023-10-16T16:00:00Z"
+            },
+            {
+                "id": 1234568,
+                "name": "Miss Healer",
+                "level": 150,
+                "vocation": "elderDruid",
+                "sex": "female",
+                "world": "Secura",
+                "bid": 500,
+                "hasBid": false,
+                "startedAt": "2023-10-13T08:00:00Z",
+                "endsAt": "2023-10-17T08:00:00Z"
+            },
+            {
+                "id": 1234569,
+                "name": "Rookstayer",
+                "level": 8,
+                "sex": "male",
+                "world": "Nostera",
+                "bid": 57,
+                "hasBid": true,
+                "startedAt": "2023-10-26T08:00:00Z",
+                "endsAt": "2023-11-02T09:00:00Z"
+            }
+        ]),
+        received_json
+    );
+}
+
+#[tokio::test]
+async fn can_get_empty_bazaar() {
+    let body = include_str!("../mocks/bazaar-empty-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/bazaar?world=Antica&vocation=knight&minLevel=1000"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(json!([]), received_json);
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_page() {
+    let body = include_str!("../mocks/bazaar-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/bazaar?page=0"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/bazaar"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+}
@@ -0,0 +1,105 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn can_get_multiple_characters() {
+    let client = MockedClient::new()
+        .body(include_str!(
+            "../mocks/character-invalid_character-200.html"
+        ))
+        .character_body(
+            "Urinchoklad",
+            include_str!("../mocks/character-urinchoklad-200.html"),
+        )
+        .character_body("Lecca", include_str!("../mocks/character-lecca-200.html"));
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/api/v1/characters"))
+        .json(&json!({ "names": ["Lecca", "invalid_character", "Urinchoklad"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let urinchoklad = include_str!("../mocks/character-urinchoklad-200.json");
+    let lecca = include_str!("../mocks/character-lecca-200.json");
+
+    assert_eq!(
+        json!([
+            { "name": "Lecca", "data": serde_json::from_str::<Value>(lecca).unwrap() },
+            { "name": "invalid_character", "error": "not_found" },
+            { "name": "Urinchoklad", "data": serde_json::from_str::<Value>(urinchoklad).unwrap() },
+        ]),
+        received_json
+    );
+}
+
+#[tokio::test]
+async fn reports_missing_characters() {
+    let body = include_str!("../mocks/character-invalid_character-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/api/v1/characters"))
+        .json(&json!({ "names": ["invalid_character"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(
+        json!([{ "name": "invalid_character", "error": "not_found" }]),
+        received_json
+    );
+}
+
+#[tokio::test]
+async fn returns_400_for_too_many_names() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let names = (0..11)
+        .map(|i| format!("Character {i}"))
+        .collect::<Vec<_>>();
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/api/v1/characters"))
+        .json(&json!({ "names": names }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/api/v1/characters"))
+        .json(&json!({ "names": ["Urinchoklad"] }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status())
+}
@@ -0,0 +1,358 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn can_get_a_character() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Urinchoklad"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let expected = include_str!("../mocks/character-urinchoklad-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+
+    assert_eq!(expected_json, received_json);
+}
+
+#[tokio::test]
+async fn can_parse_house_paid_until() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Urinchoklad"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("2023-09-08", received_json["houses"][0]["paidUntil"]);
+    assert_eq!("2024-01-02", received_json["houses"][1]["paidUntil"]);
+}
+
+#[tokio::test]
+async fn can_get_former_world() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html").replace(
+        r#"<td class="LabelV175">World:</td><td>Antica</td></tr>"#,
+        r##"<td class="LabelV175">World:</td><td>Antica</td></tr><tr bgcolor="#F1E0C6"><td class="LabelV175">Former World:</td><td>Secura</td></tr>"##,
+    );
+    let client = MockedClient::new().body(&body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Urinchoklad"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("/api/v1/worlds/Antica", received_json["worldUrl"]);
+    assert_eq!("Secura", received_json["formerWorld"]);
+    assert_eq!("/api/v1/worlds/Secura", received_json["formerWorldUrl"]);
+}
+
+#[tokio::test]
+async fn can_get_former_names() {
+    for (former_names, expected) in [
+        ("Urinchoklad Jr ", json!(["Urinchoklad Jr"])),
+        (
+            "Urinchoklad Jr, Old Urin,<wbr>Young Urin",
+            json!(["Urinchoklad Jr", "Old Urin", "Young Urin"]),
+        ),
+    ] {
+        let body = include_str!("../mocks/character-urinchoklad-200.html").replace(
+            "<td>Urinchoklad Jr</td>",
+            &format!("<td>{former_names}</td>"),
+        );
+        let client = MockedClient::new().body(&body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!("http://{addr}/api/v1/characters/Urinchoklad"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(expected, received_json["formerNames"]);
+    }
+}
+
+#[tokio::test]
+async fn can_get_transferred_character() {
+    let body = include_str!("../mocks/character-lecca-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Lecca"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let expected = include_str!("../mocks/character-lecca-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+
+    assert_eq!(expected_json, received_json);
+    assert_eq!("Quelibra", received_json["formerWorld"]);
+}
+
+#[tokio::test]
+async fn can_get_loyalty_title() {
+    for (character_name, body, loyalty_title) in [
+        (
+            "Lecca",
+            include_str!("../mocks/character-lecca-200.html"),
+            json!("Guardian of Tibia"),
+        ),
+        (
+            "Bubble",
+            include_str!("../mocks/character-bubble-200.html"),
+            Value::Null,
+        ),
+    ] {
+        let client = MockedClient::new().body(body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!("http://{addr}/api/v1/characters/{character_name}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(loyalty_title, received_json["loyaltyTitle"]);
+    }
+}
+
+#[tokio::test]
+async fn can_get_titles_unlocked() {
+    let body = include_str!("../mocks/character-lecca-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Lecca"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(3, received_json["titlesUnlocked"]);
+    assert_eq!(None, received_json.get("title"));
+}
+
+#[tokio::test]
+async fn can_get_daily_reward_streak() {
+    for (character_name, body, daily_reward_streak) in [
+        (
+            "Lecca",
+            include_str!("../mocks/character-lecca-200.html"),
+            json!(42),
+        ),
+        (
+            "Bubble",
+            include_str!("../mocks/character-bubble-200.html"),
+            Value::Null,
+        ),
+    ] {
+        let client = MockedClient::new().body(body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!("http://{addr}/api/v1/characters/{character_name}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(daily_reward_streak, received_json["dailyRewardStreak"]);
+    }
+}
+
+#[tokio::test]
+async fn can_get_charm_expansion_and_hunting_task_points() {
+    for (character_name, body, charm_expansion, hunting_task_points) in [
+        (
+            "Lecca",
+            include_str!("../mocks/character-lecca-200.html"),
+            json!(true),
+            json!(5000),
+        ),
+        (
+            "Bubble",
+            include_str!("../mocks/character-bubble-200.html"),
+            Value::Null,
+            Value::Null,
+        ),
+    ] {
+        let client = MockedClient::new().body(body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!("http://{addr}/api/v1/characters/{character_name}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(charm_expansion, received_json["charmExpansion"]);
+        assert_eq!(hunting_task_points, received_json["huntingTaskPoints"]);
+    }
+}
+
+#[tokio::test]
+async fn can_get_account_status() {
+    for (character_name, body, account_status) in [
+        (
+            "Urinchoklad",
+            include_str!("../mocks/character-urinchoklad-200.html"),
+            "premium",
+        ),
+        (
+            "Bubble",
+            include_str!("../mocks/character-bubble-200.html"),
+            "free",
+        ),
+    ] {
+        let client = MockedClient::new().body(body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!("http://{addr}/api/v1/characters/{character_name}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(account_status, received_json["accountStatus"]);
+        assert_eq!(None, received_json.get("hasPremium"));
+    }
+}
+
+#[tokio::test]
+async fn can_get_residence() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Urinchoklad"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("Thais", received_json["residence"]);
+    assert_eq!(None, received_json.get("spawnPoint"));
+}
+
+#[tokio::test]
+async fn can_get_outfit() {
+    for (character_name, body, outfit) in [
+        (
+            "Lecca",
+            include_str!("../mocks/character-lecca-200.html"),
+            json!({
+                "lookType": 129,
+                "head": 95,
+                "body": 116,
+                "legs": 121,
+                "feet": 115,
+                "addons": 3,
+                "mount": 368
+            }),
+        ),
+        (
+            "Bubble",
+            include_str!("../mocks/character-bubble-200.html"),
+            Value::Null,
+        ),
+    ] {
+        let client = MockedClient::new().body(body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!("http://{addr}/api/v1/characters/{character_name}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(outfit, received_json["outfit"]);
+        assert_eq!(
+            outfit.is_object(),
+            received_json["outfitUrl"]
+                .as_str()
+                .is_some_and(|url| url.contains("id=129"))
+        );
+    }
+}
+
+#[tokio::test]
+async fn can_get_character_that_never_logged_in() {
+    let body = include_str!("../mocks/character-newbie-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Newbie"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(Some(&Value::Null), received_json.get("lastLogin"));
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_character() {
+    let body = include_str!("../mocks/character-invalid_character-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/invalid_character"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Urinchoklad"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status())
+}
@@ -0,0 +1,99 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+async fn can_get_deaths() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/characters/Urinchoklad/deaths"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let expected = include_str!("../mocks/deaths-urinchoklad-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+
+    assert_eq!(expected_json, received_json);
+}
+
+#[tokio::test]
+async fn can_paginate_deaths() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/characters/Urinchoklad/deaths?limit=2&offset=1"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let expected = include_str!("../mocks/deaths-urinchoklad-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+    let expected_json = Value::Array(expected_json.as_array().unwrap()[1..3].to_vec());
+
+    assert_eq!(expected_json, received_json);
+}
+
+#[tokio::test]
+async fn returns_empty_list_without_deaths() {
+    let body = include_str!("../mocks/character-bubble-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/characters/Bubble/deaths"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(Value::Array(vec![]), received_json);
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_character() {
+    let body = include_str!("../mocks/character-invalid_character-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/characters/invalid_character/deaths"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/characters/Urinchoklad/deaths"
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status())
+}
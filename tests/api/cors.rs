@@ -0,0 +1,87 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::{header, Method, StatusCode};
+
+#[tokio::test]
+async fn answers_preflight_requests() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::Client::new()
+        .request(Method::OPTIONS, format!("http://{addr}/api/v1/towns"))
+        .header(header::ORIGIN, "https://example.com")
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let headers = response.headers();
+    assert_eq!("*", headers[header::ACCESS_CONTROL_ALLOW_ORIGIN]);
+    let allowed_methods = headers[header::ACCESS_CONTROL_ALLOW_METHODS]
+        .to_str()
+        .unwrap();
+    assert!(allowed_methods.contains("GET"));
+    assert!(allowed_methods.contains("POST"));
+    assert_eq!(
+        "content-type",
+        headers[header::ACCESS_CONTROL_ALLOW_HEADERS]
+    );
+}
+
+#[tokio::test]
+async fn exposes_headers() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{addr}/__healthcheck"))
+        .header(header::ORIGIN, "https://example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let exposed_headers = response.headers()[header::ACCESS_CONTROL_EXPOSE_HEADERS]
+        .to_str()
+        .unwrap()
+        .split(',')
+        .map(|h| h.trim().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "x-tibia-data-age",
+            "x-request-id",
+            "x-total-count",
+            "retry-after"
+        ],
+        exposed_headers
+    );
+}
+
+#[tokio::test]
+async fn only_allows_configured_origins() {
+    let allowed_origins = AllowedOrigins::parse("https://allowed.example.com");
+    let addr = spawn_app_with_origins(AppState::default(), allowed_origins);
+
+    let request = |origin: &'static str| {
+        reqwest::Client::new()
+            .get(format!("http://{addr}/__healthcheck"))
+            .header(header::ORIGIN, origin)
+            .send()
+    };
+
+    let response = request("https://allowed.example.com").await.unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let headers = response.headers();
+    assert_eq!(
+        "https://allowed.example.com",
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN]
+    );
+    assert_eq!("true", headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS]);
+
+    let response = request("https://other.example.com").await.unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert!(!response
+        .headers()
+        .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
@@ -0,0 +1,301 @@
+//! Smoke tests running every fixture in `tests/mocks` through the parsers of the
+//! routes that use it, to catch panics and unexpected errors introduced by refactors.
+
+use std::collections::BTreeSet;
+
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+/// The fixtures, the routes that parse them and the expected response status
+const FIXTURE_ROUTES: &[(&str, &[&str], StatusCode)] = &[
+    ("bazaar-200.html", &["/api/v1/bazaar"], StatusCode::OK),
+    ("bazaar-empty-200.html", &["/api/v1/bazaar"], StatusCode::OK),
+    (
+        "character-bubble-200.html",
+        &[
+            "/api/v1/characters/Bubble",
+            "/api/v1/characters/Bubble/deaths",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "character-invalid_character-200.html",
+        &[
+            "/api/v1/characters/invalid_character",
+            "/api/v1/characters/invalid_character/deaths",
+        ],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "character-lecca-200.html",
+        &[
+            "/api/v1/characters/Lecca",
+            "/api/v1/characters/Lecca/deaths",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "character-newbie-200.html",
+        &[
+            "/api/v1/characters/Newbie",
+            "/api/v1/characters/Newbie/deaths",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "character-urinchoklad-200.html",
+        &[
+            "/api/v1/characters/Urinchoklad",
+            "/api/v1/characters/Urinchoklad/deaths",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "creature-dragon_lord-200.html",
+        &["/api/v1/creatures/Dragon%20Lord"],
+        StatusCode::OK,
+    ),
+    (
+        "creature-invalid_creature-200.html",
+        &["/api/v1/creatures/invalid_creature"],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "guild-blood_oath-200.html",
+        &["/api/v1/guilds/Blood%20Oath"],
+        StatusCode::OK,
+    ),
+    (
+        "guild-invalid_guild-200.html",
+        &["/api/v1/guilds/invalid_guild"],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "guild-red_rose-200.html",
+        &["/api/v1/guilds/Red%20Rose"],
+        StatusCode::OK,
+    ),
+    (
+        "guilds-invalid_world-200.html",
+        &[
+            "/api/v1/worlds/invalid_world/guilds",
+            "/api/v1/worlds/invalid_world/guilds/Red%20Rose",
+            "/api/v1/worlds/invalid_world/guilds/Red%20Rose/members",
+        ],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "guilds-jaguna-200.html",
+        &["/api/v1/worlds/Jaguna/guilds"],
+        StatusCode::OK,
+    ),
+    (
+        "highscores-antica-experience-200.html",
+        &[
+            "/api/v1/worlds/Antica/highscores/experience",
+            "/api/v1/worlds/Antica/highscores/loyaltyPoints?page=2&vocation=knight",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "highscores-invalid_world-200.html",
+        &["/api/v1/worlds/invalid_world/highscores/experience"],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "houses-invalid_world-edron-200.html",
+        &[
+            "/api/v1/worlds/invalid_world/residences?town=Edron&type=house",
+            "/api/v1/residences/auctions?worlds=invalid_world&town=Edron",
+        ],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "houses-jaguna-edron-200.html",
+        &[
+            "/api/v1/worlds/Jaguna/residences?town=Edron&type=house",
+            "/api/v1/worlds/Jaguna/residences?town=Edron&type=house&format=csv",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "houses-jaguna-edron-ending-200.html",
+        &[
+            "/api/v1/worlds/Jaguna/residences?town=Edron&type=house",
+            "/api/v1/residences/auctions?worlds=Jaguna&town=Edron&type=house",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "houses-jaguna-invalid_town-200.html",
+        &["/api/v1/worlds/Jaguna/residences?town=invalid_town&type=house"],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "killstatistics-antica-200.html",
+        &[
+            "/api/v1/worlds/Antica/kill-statistics",
+            "/api/v1/worlds/Antica/kill-statistics/Dragons",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "killstatistics-invalid_world-200.html",
+        &[
+            "/api/v1/worlds/invalid_world/kill-statistics",
+            "/api/v1/worlds/invalid_world/kill-statistics/Dragons",
+        ],
+        StatusCode::NOT_FOUND,
+    ),
+    (
+        "maintenance-200.html",
+        &[
+            "/api/v1/bazaar",
+            "/api/v1/characters/Urinchoklad",
+            "/api/v1/characters/Urinchoklad/deaths",
+            "/api/v1/creatures/Dragon%20Lord",
+            "/api/v1/guilds/Red%20Rose",
+            "/api/v1/news.rss",
+            "/api/v1/residences/auctions?worlds=Antica&town=Edron",
+            "/api/v1/spells",
+            "/api/v1/towns",
+            "/api/v1/worlds",
+            "/api/v1/worlds/compare?worlds=Antica,Secura",
+            "/api/v1/worlds/Antica",
+            "/api/v1/worlds/Antica/guilds",
+            "/api/v1/worlds/Antica/guilds/Red%20Rose",
+            "/api/v1/worlds/Antica/guilds/Red%20Rose/members",
+            "/api/v1/worlds/Antica/highscores/experience",
+            "/api/v1/worlds/Antica/kill-statistics",
+            "/api/v1/worlds/Antica/kill-statistics/Dragons",
+            "/api/v1/worlds/Antica/online-players",
+            "/api/v1/worlds/Antica/players-online/record",
+            "/api/v1/worlds/Antica/quest-titles",
+            "/api/v1/worlds/Antica/residences?town=Edron&type=house",
+            "/api/v1/worlds/Antica/summary",
+        ],
+        StatusCode::SERVICE_UNAVAILABLE,
+    ),
+    ("news-200.html", &["/api/v1/news.rss"], StatusCode::OK),
+    ("spells-200.html", &["/api/v1/spells"], StatusCode::OK),
+    ("towns-200.html", &["/api/v1/towns"], StatusCode::OK),
+    ("towns-edron-200.html", &["/api/v1/towns"], StatusCode::OK),
+    (
+        "world-antica-200.html",
+        &[
+            "/api/v1/worlds/Antica",
+            "/api/v1/worlds/Antica/online-players",
+            "/api/v1/worlds/Antica/players-online/record",
+            "/api/v1/worlds/Antica/quest-titles",
+            "/api/v1/worlds/compare?worlds=Antica,Secura",
+        ],
+        StatusCode::OK,
+    ),
+    (
+        "world-invalid_world-200.html",
+        &[
+            "/api/v1/worlds/invalid_world",
+            "/api/v1/worlds/invalid_world/online-players",
+            "/api/v1/worlds/invalid_world/players-online/record",
+            "/api/v1/worlds/invalid_world/quest-titles",
+            "/api/v1/worlds/invalid_world/summary",
+        ],
+        StatusCode::NOT_FOUND,
+    ),
+    ("worlds-200.html", &["/api/v1/worlds"], StatusCode::OK),
+    (
+        "worlds-battleye-200.html",
+        &["/api/v1/worlds"],
+        StatusCode::OK,
+    ),
+    (
+        "worlds-tournament-200.html",
+        &["/api/v1/worlds"],
+        StatusCode::OK,
+    ),
+    (
+        "worlds-offline-200.html",
+        &["/api/v1/worlds?includeOffline=true"],
+        StatusCode::OK,
+    ),
+];
+
+/// Reads a fixture, converting iso-8859-1 pages to utf-8 like tibia.com serves them
+fn read_fixture(file_name: &str) -> String {
+    let bytes = std::fs::read(format!("tests/mocks/{file_name}")).unwrap();
+    match String::from_utf8(bytes) {
+        Ok(body) => body,
+        Err(e) => e.into_bytes().iter().map(|c| *c as char).collect(),
+    }
+}
+
+#[test]
+fn every_fixture_has_routes() {
+    let fixtures = std::fs::read_dir("tests/mocks")
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|file_name| file_name.ends_with(".html"))
+        .collect::<BTreeSet<_>>();
+    let covered = FIXTURE_ROUTES
+        .iter()
+        .map(|(file_name, _, _)| file_name.to_string())
+        .collect::<BTreeSet<_>>();
+
+    assert_eq!(
+        fixtures, covered,
+        "every fixture in tests/mocks should be listed in FIXTURE_ROUTES"
+    );
+}
+
+#[tokio::test]
+async fn every_fixture_parses_with_the_expected_status() {
+    for (file_name, routes, expected_status) in FIXTURE_ROUTES {
+        let body = read_fixture(file_name);
+        let client = MockedClient::new().body(&body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        for route in routes.iter() {
+            // a panicking parser drops the connection, failing the request
+            let response = reqwest::get(format!("http://{addr}{route}"))
+                .await
+                .unwrap_or_else(|e| panic!("{route} with {file_name} failed: {e}"));
+
+            assert_eq!(
+                *expected_status,
+                response.status(),
+                "unexpected status for {route} with {file_name}"
+            );
+            if *expected_status == StatusCode::NOT_FOUND {
+                let received_json = response.json::<Value>().await.unwrap();
+                assert_eq!(
+                    json!({
+                        "code": "RESOURCE_NOT_FOUND",
+                        "message": "The requested resource was not found."
+                    }),
+                    received_json,
+                    "unexpected body for {route} with {file_name}"
+                );
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn unknown_routes_respond_with_not_found_body() {
+    let client = MockedClient::new();
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    for route in ["/api/v1/unknown", "/openapi/v0.json"] {
+        let response = reqwest::get(format!("http://{addr}{route}")).await.unwrap();
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!("RESOURCE_NOT_FOUND", received_json["code"]);
+    }
+}
@@ -0,0 +1,224 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn can_get_a_guild() {
+    let body = include_str!("../mocks/guild-red_rose-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/guilds/Red%20Rose"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("Red Rose", received_json["name"]);
+    // the guild page doesn't tell the world to link to
+    assert_eq!(None, received_json.get("guildUrl"));
+    assert_eq!(
+        "https://www.tibia.com/community/?subtopic=guilds&page=view&GuildName=Red%20Rose",
+        received_json["tibiaUrl"]
+    );
+    assert_eq!(
+        "https://static.tibia.com/images/guildlogos/Red_Rose.gif",
+        received_json["logo"]
+    );
+    assert_eq!(
+        "Hunting dragons together since 2019.",
+        received_json["description"]
+    );
+    assert_eq!(true, received_json["applicationOpen"]);
+    assert_eq!("2019-06-24", received_json["createdAt"]);
+    assert_eq!("Urinchoklad", received_json["founder"]);
+    assert_eq!(5, received_json["memberCount"]);
+    assert_eq!(
+        5,
+        received_json["members"]["data"].as_array().unwrap().len()
+    );
+    assert_eq!(
+        json!({
+            "name": "Urinchoklad",
+            "title": "Dragon Slayer",
+            "rank": "Leader",
+            "vocation": "eliteKnight",
+            "level": 52,
+            "joined": "2019-06-24",
+            "isOnline": true
+        }),
+        received_json["members"]["data"][0]
+    );
+    assert_eq!(json!([]), received_json["wars"]);
+}
+
+#[tokio::test]
+async fn can_get_guild_wars() {
+    let body = include_str!("../mocks/guild-blood_oath-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/guilds/Blood%20Oath"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(false, received_json["applicationOpen"]);
+    assert_eq!("2018-02-11", received_json["createdAt"]);
+    assert_eq!("Hälge", received_json["founder"]);
+    assert_eq!(2, received_json["memberCount"]);
+    assert_eq!(
+        json!([
+            {
+                "opponentGuild": "Red Rose",
+                "killsUs": 9,
+                "killsThem": 14,
+                "status": "war"
+            },
+            {
+                "opponentGuild": "Black Crown",
+                "killsUs": 3,
+                "killsThem": 3,
+                "status": "truce"
+            }
+        ]),
+        received_json["wars"]
+    );
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_guild() {
+    let body = include_str!("../mocks/guild-invalid_guild-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/guilds/invalid_guild"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/guilds/Red%20Rose"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+}
+
+#[tokio::test]
+async fn can_paginate_members() {
+    let body = include_str!("../mocks/guild-red_rose-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let mut pages = vec![];
+    for page in [1, 2] {
+        let response = reqwest::get(format!(
+            "http://{addr}/api/v1/guilds/Red%20Rose?page={page}&perPage=2"
+        ))
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(5, received_json["memberCount"]);
+        assert_eq!(page, received_json["members"]["page"]);
+        assert_eq!(5, received_json["members"]["totalItems"]);
+        assert_eq!(3, received_json["members"]["totalPages"]);
+        pages.push(received_json["members"]["data"].clone());
+    }
+
+    let names = |page: &Value| {
+        page.as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(vec!["Urinchoklad", "Mad Bubble"], names(&pages[0]));
+    assert_eq!(vec!["Elder Bob", "Sir Helper"], names(&pages[1]));
+}
+
+#[tokio::test]
+async fn returns_no_members_far_past_the_last_page() {
+    let body = include_str!("../mocks/guild-red_rose-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/guilds/Red%20Rose?page=4294967295&perPage=100"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(5, received_json["members"]["totalItems"]);
+    assert_eq!(
+        Value::from(Vec::<Value>::new()),
+        received_json["members"]["data"]
+    );
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_per_page() {
+    let body = include_str!("../mocks/guild-red_rose-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/guilds/Red%20Rose?perPage=101"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn can_filter_members_by_rank() {
+    let body = include_str!("../mocks/guild-red_rose-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/guilds/Red%20Rose?hasRank=Vice%20Leader"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let members = received_json["members"]["data"].as_array().unwrap();
+    assert_eq!(2, members.len());
+    assert!(members.iter().all(|m| m["rank"] == "Vice Leader"));
+    assert_eq!(2, received_json["members"]["totalItems"]);
+    assert_eq!(5, received_json["memberCount"]);
+}
@@ -0,0 +1,24 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn lists_the_documented_endpoints() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::get(format!("http://{addr}/api/v1")).await.unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let endpoints = received_json["endpoints"].as_array().unwrap();
+    assert!(endpoints.contains(&json!({
+        "method": "GET",
+        "path": "/api/v1/worlds/{world_name}",
+        "description": "World"
+    })));
+    assert!(endpoints
+        .iter()
+        .any(|e| e["method"] == "POST" && e["path"] == "/api/v1/characters"));
+    assert!(endpoints.iter().all(|e| e["description"].is_string()));
+}
@@ -1,14 +1,44 @@
 use once_cell::sync::Lazy;
-use tibia_api::{app, clients::Client, run, telemetry, AppState};
+use tibia_api::{app, clients::Client, run, telemetry, AllowedOrigins, AppState};
 
 mod __healthcheck;
+mod __version;
+mod bazaar;
+mod characters;
+mod characters_character_name;
+mod characters_character_name_deaths;
+mod cors;
+mod creatures_creature_name;
+mod fixtures;
+mod guilds_guild_name;
+mod index;
 mod mocked_client;
+mod models;
+mod news_rss;
+mod openapi;
+mod request_id;
+mod residences_auctions;
+mod server_save;
+mod shutdown;
+mod spans;
+mod spells;
 mod towns;
+mod upstream_errors;
 mod worlds;
+mod worlds_compare;
 mod worlds_world_name;
 mod worlds_world_name_guilds;
+mod worlds_world_name_guilds_guild_name;
+mod worlds_world_name_guilds_guild_name_members;
+mod worlds_world_name_highscores_category;
 mod worlds_world_name_kill_statistics;
+mod worlds_world_name_kill_statistics_race;
+mod worlds_world_name_online_players;
+mod worlds_world_name_players_online_record;
+mod worlds_world_name_quest_titles;
 mod worlds_world_name_residences;
+mod worlds_world_name_summary;
+mod ws_worlds_world_name_players_online;
 
 pub use mocked_client::*;
 
@@ -28,12 +58,19 @@ static TRACING: Lazy<()> = Lazy::new(|| {
 });
 
 pub fn spawn_app<C: Client>(state: AppState<C>) -> std::net::SocketAddr {
+    spawn_app_with_origins(state, AllowedOrigins::Any)
+}
+
+pub fn spawn_app_with_origins<C: Client>(
+    state: AppState<C>,
+    allowed_origins: AllowedOrigins,
+) -> std::net::SocketAddr {
     Lazy::force(&TRACING);
 
     let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("To bind to random port");
     let addr = listener.local_addr().expect("To get local address");
 
-    let app = app(state);
+    let app = app(state, allowed_origins);
     tokio::spawn(run(app, listener));
 
     addr
@@ -1,13 +1,29 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use http::response;
 use tibia_api::{
     clients::{Client, TibiaError},
-    models::ResidenceType,
+    models::{BazaarFilters, HighscoreCategory, ResidenceType, Vocation},
+    DataAge,
 };
 
 #[derive(Clone)]
 pub struct MockedClient {
-    status: reqwest::StatusCode,
+    /// The status of every fetch, shared between clones
+    status: Arc<Mutex<reqwest::StatusCode>>,
     body: Option<String>,
+    page_bodies: HashMap<&'static str, String>,
+    character_bodies: HashMap<String, String>,
+    /// How long every fetch takes
+    delay: Duration,
+    /// How long ago the pages were fetched, like pages from the cache of `TibiaClient`
+    age: Option<Duration>,
+    /// The number of times each page was fetched, shared between clones
+    fetches: Arc<Mutex<HashMap<&'static str, usize>>>,
 }
 
 impl MockedClient {
@@ -15,7 +31,14 @@ impl MockedClient {
         Self::default()
     }
     pub fn status(self, status: reqwest::StatusCode) -> Self {
-        Self { status, ..self }
+        Self {
+            status: Arc::new(Mutex::new(status)),
+            ..self
+        }
+    }
+    /// Changes the status of this client and its clones, like tibia.com going down
+    pub fn set_status(&self, status: reqwest::StatusCode) {
+        *self.status.lock().unwrap() = status;
     }
     pub fn body(self, body: &str) -> Self {
         Self {
@@ -23,24 +46,87 @@ impl MockedClient {
             ..self
         }
     }
+    /// Makes every fetch take `delay`, like a slow tibia.com
+    pub fn delay(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+    /// Serves the pages as if they were cached `age` ago
+    pub fn age(self, age: Duration) -> Self {
+        Self {
+            age: Some(age),
+            ..self
+        }
+    }
+    /// Overrides the body for a single `fetch_*` method, e.g. `"fetch_guild_page"`
+    pub fn page_body(mut self, page: &'static str, body: &str) -> Self {
+        self.page_bodies.insert(page, body.into());
+        self
+    }
 
-    fn mocked(&self) -> Result<reqwest::Response, TibiaError> {
-        let body = self.body.clone().unwrap_or_default();
-        let response = response::Response::builder()
-            .status(self.status)
-            .body(body)
+    /// Overrides the body of the page of a single character
+    pub fn character_body(mut self, name: &str, body: &str) -> Self {
+        self.character_bodies.insert(name.into(), body.into());
+        self
+    }
+
+    /// The number of times this client and its clones fetched a page, e.g. `"fetch_guild_page"`
+    pub fn fetches(&self, page: &'static str) -> usize {
+        self.fetches
+            .lock()
             .unwrap()
-            .into();
+            .get(page)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    async fn mocked(&self, page: &'static str) -> Result<reqwest::Response, TibiaError> {
+        self.mocked_with(page, None).await
+    }
+
+    async fn mocked_with(
+        &self,
+        page: &'static str,
+        body: Option<&String>,
+    ) -> Result<reqwest::Response, TibiaError> {
+        *self.fetches.lock().unwrap().entry(page).or_default() += 1;
+        tokio::time::sleep(self.delay).await;
+        let body = body
+            .or(self.page_bodies.get(page))
+            .or(self.body.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        let status = *self.status.lock().unwrap();
+        // like `TibiaClient`, error statuses are returned as errors
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(TibiaError::RateLimited(None));
+        }
+        if status.is_client_error() || status.is_server_error() {
+            return Err(TibiaError::UnsuccessfulRequest(status));
+        }
+        let mut response = response::Response::builder()
+            .status(status)
+            .body(body)
+            .unwrap();
+        if let Some(age) = self.age {
+            response
+                .extensions_mut()
+                .insert(DataAge::fetched_at(Instant::now() - age));
+        }
 
-        Ok(response)
+        Ok(response.into())
     }
 }
 
 impl Default for MockedClient {
     fn default() -> Self {
         Self {
-            status: reqwest::StatusCode::OK,
+            status: Arc::new(Mutex::new(reqwest::StatusCode::OK)),
             body: None,
+            page_bodies: HashMap::new(),
+            character_bodies: HashMap::new(),
+            delay: Duration::ZERO,
+            age: None,
+            fetches: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -48,29 +134,33 @@ impl Default for MockedClient {
 #[async_trait::async_trait]
 impl Client for MockedClient {
     async fn fetch_towns_page(&self) -> Result<reqwest::Response, TibiaError> {
-        self.mocked()
+        self.mocked("fetch_towns_page").await
     }
 
     async fn fetch_worlds_page(&self) -> Result<reqwest::Response, TibiaError> {
-        self.mocked()
+        self.mocked("fetch_worlds_page").await
+    }
+
+    async fn probe_worlds_page(&self) -> Result<reqwest::Response, TibiaError> {
+        self.mocked("probe_worlds_page").await
     }
 
     async fn fetch_world_details_page(
         &self,
         _world_name: &str,
     ) -> Result<reqwest::Response, TibiaError> {
-        self.mocked()
+        self.mocked("fetch_world_details_page").await
     }
 
     async fn fetch_guilds_page(&self, _world_name: &str) -> Result<reqwest::Response, TibiaError> {
-        self.mocked()
+        self.mocked("fetch_guilds_page").await
     }
 
     async fn fetch_killstatistics_page(
         &self,
         _world_name: &str,
     ) -> Result<reqwest::Response, TibiaError> {
-        self.mocked()
+        self.mocked("fetch_killstatistics_page").await
     }
 
     async fn fetch_residences_page(
@@ -79,6 +169,54 @@ impl Client for MockedClient {
         _residence_type: &ResidenceType,
         _town: &str,
     ) -> Result<reqwest::Response, TibiaError> {
-        self.mocked()
+        self.mocked("fetch_residences_page").await
+    }
+
+    async fn fetch_character_page(
+        &self,
+        character_name: &str,
+    ) -> Result<reqwest::Response, TibiaError> {
+        self.mocked_with(
+            "fetch_character_page",
+            self.character_bodies.get(character_name),
+        )
+        .await
+    }
+
+    async fn fetch_guild_page(&self, _guild_name: &str) -> Result<reqwest::Response, TibiaError> {
+        self.mocked("fetch_guild_page").await
+    }
+
+    async fn fetch_creature_page(
+        &self,
+        _creature_name: &str,
+    ) -> Result<reqwest::Response, TibiaError> {
+        self.mocked("fetch_creature_page").await
+    }
+
+    async fn fetch_spells_page(&self) -> Result<reqwest::Response, TibiaError> {
+        self.mocked("fetch_spells_page").await
+    }
+
+    async fn fetch_bazaar_page(
+        &self,
+        _page: u32,
+        _filters: &BazaarFilters,
+    ) -> Result<reqwest::Response, TibiaError> {
+        self.mocked("fetch_bazaar_page").await
+    }
+
+    async fn fetch_news_page(&self) -> Result<reqwest::Response, TibiaError> {
+        self.mocked("fetch_news_page").await
+    }
+
+    async fn fetch_highscores_page(
+        &self,
+        _world_name: &str,
+        _category: &HighscoreCategory,
+        _vocation: Option<Vocation>,
+        _page: u32,
+    ) -> Result<reqwest::Response, TibiaError> {
+        self.mocked("fetch_highscores_page").await
     }
 }
@@ -0,0 +1,63 @@
+//! The models can be deserialized from the json this api responds with, so
+//! that rust clients can share them with the server.
+
+use pretty_assertions::assert_eq;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tibia_api::models::*;
+
+/// Asserts that `json` deserializes into `T`, which serializes back into the same json
+fn assert_round_trips<T: Serialize + DeserializeOwned>(json: &str) {
+    let expected = serde_json::from_str::<Value>(json).unwrap();
+
+    let model = serde_json::from_str::<T>(json).unwrap();
+    let received = serde_json::to_value(model).unwrap();
+
+    assert_eq!(expected, received);
+}
+
+#[test]
+fn worlds_round_trip() {
+    assert_round_trips::<WorldsResponse>(include_str!("../mocks/worlds-200.json"));
+}
+
+#[test]
+fn world_details_round_trip() {
+    assert_round_trips::<WorldDetails>(include_str!("../mocks/world-antica-200.json"));
+}
+
+#[test]
+fn kill_statistics_round_trip() {
+    assert_round_trips::<KillStatistics>(include_str!("../mocks/killstatistics-antica-200.json"));
+}
+
+#[test]
+fn guilds_round_trip() {
+    assert_round_trips::<Vec<Guild>>(include_str!("../mocks/guilds-jaguna-200.json"));
+}
+
+#[test]
+fn residences_round_trip() {
+    assert_round_trips::<Vec<Residence>>(include_str!("../mocks/houses-jaguna-edron-200.json"));
+}
+
+#[test]
+fn characters_round_trip() {
+    assert_round_trips::<CharacterInfo>(include_str!("../mocks/character-urinchoklad-200.json"));
+    assert_round_trips::<CharacterInfo>(include_str!("../mocks/character-lecca-200.json"));
+}
+
+#[test]
+fn deaths_round_trip() {
+    assert_round_trips::<Vec<Death>>(include_str!("../mocks/deaths-urinchoklad-200.json"));
+}
+
+#[test]
+fn creatures_round_trip() {
+    assert_round_trips::<Creature>(include_str!("../mocks/creature-dragon_lord-200.json"));
+}
+
+#[test]
+fn spells_round_trip() {
+    assert_round_trips::<Vec<Spell>>(include_str!("../mocks/spells-200.json"));
+}
@@ -0,0 +1,59 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn can_get_news_feed() {
+    let body = include_str!("../mocks/news-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/news.rss"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("application/rss+xml", response.headers()["content-type"]);
+
+    let text = response.text().await.unwrap();
+    let channel = rss::Channel::read_from(text.as_bytes()).expect("To be valid RSS");
+    let items = channel.items();
+
+    assert_eq!(3, items.len());
+    assert_eq!(Some("Summer Update 2023 Teaser"), items[0].title());
+    assert_eq!(
+        Some("https://www.tibia.com/news/?subtopic=newsarchive&id=7412"),
+        items[0].link()
+    );
+    // news without an article of their own link to the latest news
+    assert_eq!(
+        Some("https://www.tibia.com/news/?subtopic=latestnews"),
+        items[1].link()
+    );
+    assert_eq!(None, items[1].guid());
+    assert_eq!(Some("Thu, 14 Sep 2023 00:00:00 +0200"), items[0].pub_date());
+    assert_eq!("Development", items[0].categories()[0].name());
+    assert_eq!("Technical Issues", items[2].categories()[0].name());
+    assert_eq!(
+        Some(
+            "The votes are in and the winners of this year's fansite contest have been determined."
+        ),
+        items[1].description()
+    );
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/news.rss"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+}
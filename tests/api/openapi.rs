@@ -0,0 +1,50 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+async fn serves_versioned_openapi() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::get(format!("http://{addr}/openapi/v1.json"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("Tibia API", received_json["info"]["title"]);
+}
+
+#[tokio::test]
+async fn serves_latest_openapi_as_openapi_json() {
+    let addr = spawn_app(AppState::default());
+
+    let latest = reqwest::get(format!("http://{addr}/openapi.json"))
+        .await
+        .unwrap()
+        .json::<Value>()
+        .await
+        .unwrap();
+    let v1 = reqwest::get(format!("http://{addr}/openapi/v1.json"))
+        .await
+        .unwrap()
+        .json::<Value>()
+        .await
+        .unwrap();
+
+    assert_eq!(v1, latest);
+}
+
+#[tokio::test]
+async fn returns_404_for_unknown_version() {
+    let addr = spawn_app(AppState::default());
+
+    for file_name in ["v2.json", "v1"] {
+        let response = reqwest::get(format!("http://{addr}/openapi/{file_name}"))
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+}
@@ -0,0 +1,74 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn sends_request_id_header() {
+    let body = include_str!("../mocks/towns-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    for (route, status) in [
+        ("/__healthcheck", StatusCode::OK),
+        ("/api/v1/towns", StatusCode::OK),
+        ("/api/v1/characters/Urinchoklad", StatusCode::NOT_FOUND),
+        ("/does-not-exist", StatusCode::NOT_FOUND),
+    ] {
+        let response = reqwest::get(format!("http://{addr}{route}")).await.unwrap();
+        assert_eq!(status, response.status(), "unexpected status for {route}");
+
+        let request_id = response.headers().get("x-request-id");
+        assert!(
+            request_id.is_some_and(|id| !id.is_empty()),
+            "missing request id for {route}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn generates_unique_request_ids() {
+    let addr = spawn_app(AppState::default());
+
+    let mut request_ids = vec![];
+    for _ in 0..2 {
+        let response = reqwest::get(format!("http://{addr}/__healthcheck"))
+            .await
+            .unwrap();
+        request_ids.push(response.headers()["x-request-id"].clone());
+    }
+
+    assert_ne!(request_ids[0], request_ids[1]);
+}
+
+#[tokio::test]
+async fn reuses_inbound_request_id() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{addr}/__healthcheck"))
+        .header("x-request-id", "my-request-id")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!("my-request-id", response.headers()["x-request-id"]);
+}
+
+#[tokio::test]
+async fn replaces_oversized_inbound_request_id() {
+    let addr = spawn_app(AppState::default());
+    let inbound = "a".repeat(129);
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{addr}/__healthcheck"))
+        .header("x-request-id", &inbound)
+        .send()
+        .await
+        .unwrap();
+
+    let request_id = response.headers()["x-request-id"].to_str().unwrap();
+    assert!(!request_id.is_empty());
+    assert_ne!(inbound, request_id);
+}
@@ -0,0 +1,132 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+async fn can_get_auctions_ending_first() {
+    let body = include_str!("../mocks/houses-jaguna-edron-ending-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/residences/auctions?worlds=jaguna&town=Edron&type=house"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let auctions = received_json.as_array().unwrap();
+    assert!(!auctions.is_empty());
+    assert!(auctions.iter().all(|a| a["world"] == "Jaguna"));
+    assert!(auctions.iter().all(|a| {
+        let status = a["status"]["type"].as_str().unwrap();
+        status == "auctionWithBid" || status == "auctionNoBid"
+    }));
+
+    // the auctions with bids end first, the ones without bids go last
+    let expiry_times = auctions
+        .iter()
+        .map(|a| a["status"]["expiryTime"].as_str())
+        .collect::<Vec<_>>();
+    let with_bids = expiry_times.iter().take_while(|t| t.is_some()).count();
+    assert!(with_bids > 0 && with_bids < expiry_times.len());
+    assert!(expiry_times[with_bids..].iter().all(|t| t.is_none()));
+    assert!(expiry_times[..with_bids].windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[tokio::test]
+async fn returns_400_without_worlds() {
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/residences/auctions?worlds=&town=Edron"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn returns_400_for_too_many_worlds() {
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/residences/auctions?worlds=Antica,Secura,Nostera,Jaguna,Vunira,Peloria&town=Edron"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_world() {
+    let body = include_str!("../mocks/houses-invalid_world-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/residences/auctions?worlds=invalid_world&town=Edron"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn scans_each_world_once() {
+    let body = include_str!("../mocks/houses-jaguna-edron-ending-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client.clone());
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/residences/auctions?worlds=jaguna,Jaguna&town=Edron&type=house"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    assert_eq!(1, client.fetches("fetch_residences_page"));
+    let received_json = response.json::<Value>().await.unwrap();
+    let auctions = received_json.as_array().unwrap();
+    let ids = auctions
+        .iter()
+        .map(|a| a["id"].as_u64().unwrap())
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(auctions.len(), ids.len());
+}
+
+#[tokio::test]
+async fn returns_500_without_towns() {
+    let body = include_str!("../mocks/houses-jaguna-edron-ending-200.html");
+    let client = MockedClient::new().body(body);
+
+    // no towns are loaded, as happens when tibia.com was down on startup
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/residences/auctions?worlds=jaguna"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+}
@@ -0,0 +1,26 @@
+use super::*;
+use chrono::{DateTime, Utc};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+async fn can_get_next_server_save() {
+    let addr = spawn_app(AppState::default());
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/server-save"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let next_server_save = received_json["nextServerSave"]
+        .as_str()
+        .unwrap()
+        .parse::<DateTime<Utc>>()
+        .unwrap();
+    let seconds_until = received_json["secondsUntil"].as_i64().unwrap();
+
+    assert!(next_server_save > Utc::now());
+    assert!((1..=25 * 3600).contains(&seconds_until));
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use tibia_api::run_until;
+use tokio::{sync::oneshot, task::JoinHandle};
+
+/// Requests the worlds, which take `delay` to fetch, and signals a shutdown
+/// while the request is in flight. Returns the request and how long the server
+/// took to stop after the signal
+async fn shut_down_during_request(
+    delay: Duration,
+    drain_timeout: Duration,
+) -> (JoinHandle<reqwest::Result<reqwest::Response>>, Duration) {
+    let body = include_str!("../mocks/worlds-200.html");
+    let client = MockedClient::new().body(body).delay(delay);
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("To bind to random port");
+    let addr = listener.local_addr().expect("To get local address");
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server = tokio::spawn(run_until(
+        app(AppState::with_client(client.clone()), AllowedOrigins::Any),
+        listener,
+        async move {
+            let _ = shutdown_rx.await;
+        },
+        drain_timeout,
+    ));
+
+    let request = tokio::spawn(reqwest::get(format!("http://{addr}/api/v1/worlds")));
+    // the connection has to be accepted before the shutdown, or it is refused
+    while client.fetches("fetch_worlds_page") == 0 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let signalled_at = std::time::Instant::now();
+    shutdown_tx.send(()).unwrap();
+
+    server.await.unwrap().unwrap();
+    (request, signalled_at.elapsed())
+}
+
+#[tokio::test]
+async fn finishes_in_flight_requests_before_shutting_down() {
+    let (request, _) =
+        shut_down_during_request(Duration::from_millis(500), Duration::from_secs(5)).await;
+
+    assert_eq!(StatusCode::OK, request.await.unwrap().unwrap().status());
+}
+
+#[tokio::test]
+async fn stops_waiting_for_requests_after_the_timeout() {
+    let (request, shutdown_took) =
+        shut_down_during_request(Duration::from_secs(5), Duration::from_millis(100)).await;
+
+    assert!(shutdown_took < Duration::from_secs(2));
+    assert!(!request.is_finished());
+}
@@ -0,0 +1,79 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use super::*;
+use reqwest::StatusCode;
+
+/// Collects the logs written by a subscriber
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Requests the route with the logs of the app collected, the app runs on the
+/// current thread so that the subscriber applies to it
+fn logs_of(client: MockedClient, route: &str) -> String {
+    let logs = Logs::default();
+    let sink = logs.clone();
+    let subscriber =
+        telemetry::get_subscriber("test".to_string(), "info".to_string(), move || sink.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let state = AppState::with_client(client);
+            let addr = spawn_app(state);
+
+            let response = reqwest::get(format!("http://{addr}{route}")).await.unwrap();
+            assert_eq!(StatusCode::OK, response.status());
+        });
+    });
+
+    let logs = logs.0.lock().unwrap();
+    String::from_utf8_lossy(&logs).to_string()
+}
+
+#[test]
+fn logs_the_spans_of_the_character_parsers() {
+    let body = include_str!("../mocks/character-urinchoklad-200.html");
+    let logs = logs_of(
+        MockedClient::new().body(body),
+        "/api/v1/characters/Urinchoklad/deaths",
+    );
+
+    for span in ["PARSE_CHARACTER_DEATHS_PAGE", "PARSE_CHARACTER_DEATHS"] {
+        assert!(
+            logs.contains(&format!("[{span} - START]")),
+            "{span} not logged"
+        );
+    }
+}
+
+#[test]
+fn logs_the_spans_of_the_guild_parsers() {
+    let body = include_str!("../mocks/guild-blood_oath-200.html");
+    let logs = logs_of(
+        MockedClient::new().body(body),
+        "/api/v1/guilds/Blood%20Oath",
+    );
+
+    for span in ["PARSE_GUILD_PAGE", "PARSE_MEMBERS", "PARSE_WARS"] {
+        assert!(
+            logs.contains(&format!("[{span} - START]")),
+            "{span} not logged"
+        );
+    }
+}
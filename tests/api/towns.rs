@@ -2,6 +2,7 @@ use super::*;
 use pretty_assertions::assert_eq;
 use reqwest::StatusCode;
 use serde_json::Value;
+use std::time::Duration;
 
 #[tokio::test]
 async fn can_get_towns() {
@@ -38,3 +39,42 @@ async fn sends_503_when_maintenance() {
 
     assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
 }
+
+#[tokio::test]
+async fn sends_data_age_header() {
+    let body = include_str!("../mocks/towns-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/towns"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let data_age = response
+        .headers()
+        .get("x-tibia-data-age")
+        .expect("X-Tibia-Data-Age header to be set");
+    assert!(data_age.to_str().unwrap().parse::<u64>().is_ok());
+}
+
+#[tokio::test]
+async fn sends_the_age_of_cached_pages() {
+    let body = include_str!("../mocks/towns-200.html");
+    let client = MockedClient::default()
+        .body(body)
+        .age(Duration::from_secs(120));
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/towns"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let data_age = response.headers()["x-tibia-data-age"].to_str().unwrap();
+    assert!(data_age.parse::<u64>().unwrap() >= 120);
+}
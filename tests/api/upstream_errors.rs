@@ -0,0 +1,71 @@
+//! The responses for errors of tibia.com, which are the same for every route
+
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::{header, StatusCode};
+use serde_json::Value;
+
+async fn get_worlds(client: MockedClient) -> reqwest::Response {
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    reqwest::get(format!("http://{addr}/api/v1/worlds"))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn sends_503_with_retry_after_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let response = get_worlds(MockedClient::new().body(body)).await;
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    assert!(response.headers().contains_key(header::RETRY_AFTER));
+}
+
+#[tokio::test]
+async fn sends_404_when_not_found() {
+    let body = include_str!("../mocks/world-invalid_world-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/invalid_world"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("RESOURCE_NOT_FOUND", received_json["code"]);
+}
+
+#[tokio::test]
+async fn sends_429_with_retry_after_when_rate_limited() {
+    let client = MockedClient::new().status(StatusCode::TOO_MANY_REQUESTS);
+    let response = get_worlds(client).await;
+
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!("60", response.headers()[header::RETRY_AFTER]);
+}
+
+#[tokio::test]
+async fn sends_503_with_retry_after_when_forbidden() {
+    let client = MockedClient::new().status(StatusCode::FORBIDDEN);
+    let response = get_worlds(client).await;
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    assert_eq!("30", response.headers()[header::RETRY_AFTER]);
+}
+
+#[tokio::test]
+async fn sends_503_for_other_unsuccessful_requests() {
+    for status in [StatusCode::NOT_FOUND, StatusCode::BAD_GATEWAY] {
+        let client = MockedClient::new().status(status);
+        let response = get_worlds(client).await;
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+        let received_json = response.json::<Value>().await.unwrap();
+        assert!(received_json["message"].is_string());
+    }
+}
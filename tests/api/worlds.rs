@@ -1,7 +1,7 @@
 use super::*;
 use pretty_assertions::assert_eq;
 use reqwest::StatusCode;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 #[tokio::test]
 async fn can_get_worlds() {
@@ -22,6 +22,12 @@ async fn can_get_worlds() {
     let expected_json = serde_json::from_str::<Value>(expected).unwrap();
 
     assert_eq!(expected_json, received_json);
+    // the date is only found in the battleye icon's tooltip
+    assert!(received_json["worlds"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|w| w["battlEyeDate"].is_string()));
 }
 
 #[tokio::test]
@@ -36,5 +42,315 @@ async fn sends_503_when_maintenance() {
         .await
         .unwrap();
 
-    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status())
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+
+    // maintenance happens around server save, which is at most a day away
+    let retry_after = response.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    assert!((1..=24 * 60 * 60).contains(&retry_after));
+}
+
+#[tokio::test]
+async fn excludes_offline_worlds_by_default() {
+    let body = include_str!("../mocks/worlds-offline-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let worlds = received_json["worlds"].as_array().unwrap();
+
+    assert!(worlds.iter().all(|w| w["isOnline"] == true));
+    assert!(!worlds.iter().any(|w| w["name"] == "Axera"));
+    assert_eq!(7006, received_json["playersOnlineTotal"]);
+}
+
+#[tokio::test]
+async fn can_include_offline_worlds() {
+    let body = include_str!("../mocks/worlds-offline-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds?includeOffline=true"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let axera = received_json["worlds"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|w| w["name"] == "Axera")
+        .expect("Offline world to be listed");
+
+    assert_eq!(false, axera["isOnline"]);
+    assert_eq!(0, axera["playersOnlineCount"]);
+}
+
+#[tokio::test]
+async fn returns_400_for_malformed_query() {
+    let body = include_str!("../mocks/worlds-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds?includeOffline=maybe"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let keys = received_json
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["code", "message"], keys);
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+    assert!(received_json["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid value for parameter 'includeOffline'"));
+}
+
+#[tokio::test]
+async fn can_include_delta_since_last_fetch() {
+    let body = include_str!("../mocks/worlds-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    // nothing to compare the first fetch with
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds?includeDelta=true"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(None, received_json.get("deltaSinceLastFetch"));
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds?includeDelta=true"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(
+        json!({ "playersOnlineTotal": 0, "recordPlayers": 0 }),
+        received_json["deltaSinceLastFetch"]
+    );
+}
+
+#[tokio::test]
+async fn omits_delta_by_default() {
+    let body = include_str!("../mocks/worlds-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    for _ in 0..2 {
+        let response = reqwest::get(format!("http://{addr}/api/v1/worlds"))
+            .await
+            .unwrap();
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!(None, received_json.get("deltaSinceLastFetch"));
+    }
+}
+
+#[tokio::test]
+async fn can_include_world_records() {
+    let body = include_str!("../mocks/worlds-200.html");
+    let world_body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::default()
+        .body(body)
+        .page_body("fetch_world_details_page", world_body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds?includeRecords=true"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let worlds = received_json["worlds"].as_array().unwrap();
+    assert!(!worlds.is_empty());
+    assert!(worlds.iter().all(|w| w["playersOnlineRecord"] == 1055
+        && w["playersOnlineRecordDate"] == "2020-05-01T15:58:30Z"));
+}
+
+#[tokio::test]
+async fn omits_world_records_by_default() {
+    let body = include_str!("../mocks/worlds-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let worlds = received_json["worlds"].as_array().unwrap();
+    assert!(worlds
+        .iter()
+        .all(|w| w.get("playersOnlineRecord").is_none()));
+}
+
+#[tokio::test]
+async fn can_get_battl_eye_type() {
+    let body = include_str!("../mocks/worlds-battleye-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let battl_eye = received_json["worlds"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| {
+            json!({
+                "name": w["name"],
+                "battlEye": w["battlEye"],
+                "battlEyeType": w["battlEyeType"],
+                "battlEyeDate": w["battlEyeDate"],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            json!({
+                "name": "Zuna",
+                "battlEye": false,
+                "battlEyeType": "unprotected",
+                "battlEyeDate": null,
+            }),
+            json!({
+                "name": "Axera",
+                "battlEye": true,
+                "battlEyeType": "sinceRelease",
+                "battlEyeDate": null,
+            }),
+            json!({
+                "name": "Antica",
+                "battlEye": true,
+                "battlEyeType": "sinceDate",
+                "battlEyeDate": "2017-08-29",
+            }),
+        ],
+        battl_eye
+    );
+}
+
+#[tokio::test]
+async fn can_get_tournament_worlds() {
+    let body = include_str!("../mocks/worlds-tournament-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let worlds = received_json["worlds"].as_array().unwrap();
+    assert_eq!(4, worlds.len());
+    assert!(worlds
+        .iter()
+        .filter(|w| w["name"] != "Endebra")
+        .all(|w| w["isTournamentWorld"] == false));
+
+    let tournament_world = worlds.iter().find(|w| w["name"] == "Endebra").unwrap();
+    // the tooltip of the tournament world doesn't change its other information
+    assert_eq!(
+        json!({
+            "name": "Endebra",
+            "tibiaUrl": "https://www.tibia.com/community/?subtopic=worlds&world=Endebra",
+            "playersOnlineCount": 152,
+            "isOnline": true,
+            "location": "europe",
+            "pvpType": "retroOpen",
+            "battlEye": true,
+            "battlEyeType": "sinceRelease",
+            "premiumRequired": false,
+            "gameWorldType": "regular",
+            "isTournamentWorld": true
+        }),
+        *tournament_world
+    );
+}
+
+#[tokio::test]
+async fn can_sort_worlds() {
+    let body = include_str!("../mocks/worlds-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let worlds = |sort: &'static str| async move {
+        let response = reqwest::get(format!("http://{addr}/api/v1/worlds?sort={sort}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        received_json["worlds"].as_array().unwrap().clone()
+    };
+
+    let names = worlds("nameDesc").await;
+    assert!(names
+        .windows(2)
+        .all(|w| { w[0]["name"].as_str().unwrap() >= w[1]["name"].as_str().unwrap() }));
+
+    let players = worlds("playersOnlineCountDesc").await;
+    assert!(players.windows(2).all(|w| {
+        w[0]["playersOnlineCount"].as_u64().unwrap() >= w[1]["playersOnlineCount"].as_u64().unwrap()
+    }));
+
+    let locations = worlds("location").await;
+    assert!(locations
+        .windows(2)
+        .all(|w| { w[0]["location"].as_str().unwrap() <= w[1]["location"].as_str().unwrap() }));
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_sort() {
+    let body = include_str!("../mocks/worlds-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds?sort=creationDate"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
 }
@@ -0,0 +1,91 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+async fn can_compare_worlds() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/compare?worlds=Antica,secura"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let worlds = received_json.as_array().unwrap();
+    assert_eq!(2, worlds.len());
+
+    let expected = include_str!("../mocks/world-antica-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+    for world in worlds {
+        // both worlds are served the same page, so they share the first rank
+        assert_eq!(1, world["playersOnlineRank"]);
+        assert_eq!("PvP", world["pvpTypeCategory"]);
+        assert!(world["ageDays"].as_u64().unwrap() > 0);
+        assert_eq!(
+            expected_json["playersOnlineCount"],
+            world["playersOnlineCount"]
+        );
+    }
+    assert_eq!("Antica", worlds[0]["name"]);
+    assert_eq!("Secura", worlds[1]["name"]);
+}
+
+#[tokio::test]
+async fn returns_400_for_too_few_worlds() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/compare?worlds=Antica"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn returns_400_for_too_many_worlds() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/compare?worlds=Antica,Secura,Nostera,Bona,Celesta,Dia"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_world() {
+    let body = include_str!("../mocks/world-invalid_world-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/compare?worlds=Antica,invalid_world"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
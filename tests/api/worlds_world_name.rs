@@ -23,6 +23,122 @@ async fn can_get_a_world() {
     assert_eq!(expected_json, received_json);
 }
 
+#[tokio::test]
+async fn can_limit_online_players() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica?playersLimit=2&playersOffset=1"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let expected = include_str!("../mocks/world-antica-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+
+    assert_eq!(
+        expected_json["playersOnline"].as_array().unwrap()[1..3],
+        received_json["playersOnline"].as_array().unwrap()[..]
+    );
+    // the count is still the total number of players online
+    assert_eq!(
+        expected_json["playersOnlineCount"],
+        received_json["playersOnlineCount"]
+    );
+}
+
+#[tokio::test]
+async fn returns_404_for_worlds_missing_from_the_worlds_list() {
+    let client = MockedClient::new()
+        .body(include_str!("../mocks/world-antica-200.html"))
+        .page_body(
+            "fetch_worlds_page",
+            include_str!("../mocks/worlds-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    // the mocked client would return Antica for any world
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Nonexistent"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/antica"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn fetches_any_world_until_the_worlds_are_known() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Nonexistent"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn reuses_recently_parsed_worlds() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client.clone());
+    let addr = spawn_app(state);
+
+    for route in ["Antica", "antica?playersLimit=1"] {
+        let response = reqwest::get(format!("http://{addr}/api/v1/worlds/{route}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+    }
+    assert_eq!(1, client.fetches("fetch_world_details_page"));
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Antica?noCache=true"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(2, client.fetches("fetch_world_details_page"));
+}
+
+#[tokio::test]
+async fn parses_both_creation_date_formats() {
+    for creation_date in ["October&#160;2020", "10/20"] {
+        let body = include_str!("../mocks/world-antica-200.html")
+            .replace("January&#160;1997", creation_date);
+        let client = MockedClient::new().body(&body);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Antica"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        assert_eq!("2020-10-01", received_json["creationDate"]);
+    }
+}
+
 #[tokio::test]
 async fn returns_404_for_invalid_world() {
     let body = include_str!("../mocks/world-invalid_world-200.html");
@@ -35,6 +151,9 @@ async fn returns_404_for_invalid_world() {
         .await
         .unwrap();
     assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("RESOURCE_NOT_FOUND", received_json["code"]);
 }
 
 #[tokio::test]
@@ -60,3 +60,217 @@ async fn sends_503_when_maintenance() {
 
     assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status())
 }
+
+#[tokio::test]
+async fn can_include_war_status() {
+    let file_path = "tests/mocks/guilds-jaguna-200.html";
+    let mut file = std::fs::File::open(file_path).unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).unwrap();
+    // converts iso-8859-1 to utf-8 so we can represent it as a string
+    let body = buf.iter().map(|c| *c as char).collect::<String>();
+
+    for (guild_page, at_war, application_open) in [
+        (
+            include_str!("../mocks/guild-blood_oath-200.html"),
+            true,
+            false,
+        ),
+        (
+            include_str!("../mocks/guild-red_rose-200.html"),
+            false,
+            true,
+        ),
+    ] {
+        let client = MockedClient::new()
+            .body(&body)
+            .page_body("fetch_guild_page", guild_page);
+
+        let state = AppState::with_client(client);
+        let addr = spawn_app(state);
+
+        let response = reqwest::get(format!(
+            "http://{addr}/api/v1/worlds/Jaguna/guilds?includeWarStatus=true&limit=50"
+        ))
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        let guilds = received_json.as_array().unwrap();
+        assert!(!guilds.is_empty());
+        assert!(guilds.iter().all(|g| g["atWar"] == at_war));
+        assert!(guilds
+            .iter()
+            .all(|g| g["applicationOpen"] == application_open));
+    }
+}
+
+#[tokio::test]
+async fn leaves_war_status_unknown_when_guild_fails() {
+    let file_path = "tests/mocks/guilds-jaguna-200.html";
+    let mut file = std::fs::File::open(file_path).unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).unwrap();
+    let body = buf.iter().map(|c| *c as char).collect::<String>();
+
+    let client = MockedClient::new().body(&body).page_body(
+        "fetch_guild_page",
+        include_str!("../mocks/guild-invalid_guild-200.html"),
+    );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds?includeWarStatus=true&limit=5"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let guilds = received_json.as_array().unwrap();
+    assert_eq!(5, guilds.len());
+    assert!(guilds.iter().all(|g| g.get("atWar").is_none()));
+}
+
+#[tokio::test]
+async fn requires_limit_with_war_status() {
+    let client = MockedClient::new();
+
+    let state = AppState::with_client(client.clone());
+    let addr = spawn_app(state);
+
+    for query in ["includeWarStatus=true", "includeWarStatus=true&limit=51"] {
+        let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Jaguna/guilds?{query}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+    assert_eq!(0, client.fetches("fetch_guild_page"));
+}
+
+#[tokio::test]
+async fn can_filter_by_active() {
+    let file_path = "tests/mocks/guilds-jaguna-200.html";
+    let mut file = std::fs::File::open(file_path).unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).unwrap();
+    let body = buf.iter().map(|c| *c as char).collect::<String>();
+
+    let client = MockedClient::new().body(&body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    for (active, expected_count) in [(true, 21), (false, 1)] {
+        let response = reqwest::get(format!(
+            "http://{addr}/api/v1/worlds/Jaguna/guilds?active={active}"
+        ))
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let received_json = response.json::<Value>().await.unwrap();
+        let guilds = received_json.as_array().unwrap();
+        assert_eq!(expected_count, guilds.len());
+        assert!(guilds.iter().all(|g| g["active"] == active));
+    }
+}
+
+#[tokio::test]
+async fn can_limit_guilds() {
+    let file_path = "tests/mocks/guilds-jaguna-200.html";
+    let mut file = std::fs::File::open(file_path).unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).unwrap();
+    let body = buf.iter().map(|c| *c as char).collect::<String>();
+
+    let client = MockedClient::new().body(&body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let all_guilds = reqwest::get(format!("http://{addr}/api/v1/worlds/Jaguna/guilds"))
+        .await
+        .unwrap()
+        .json::<Value>()
+        .await
+        .unwrap();
+
+    for (query, expected_count) in [("limit=5", 5), ("limit=5&offset=20", 2), ("offset=1000", 0)] {
+        let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Jaguna/guilds?{query}"))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("22", response.headers()["x-total-count"], "{query}");
+
+        let received_json = response.json::<Value>().await.unwrap();
+        let guilds = received_json.as_array().unwrap();
+        assert_eq!(expected_count, guilds.len(), "{query}");
+    }
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds?limit=5&offset=1"
+    ))
+    .await
+    .unwrap();
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(
+        all_guilds.as_array().unwrap()[1..6],
+        received_json.as_array().unwrap()[..]
+    );
+}
+
+#[tokio::test]
+async fn returns_400_for_too_large_limit() {
+    let client = MockedClient::new();
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds?limit=501"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn returns_absolute_logo_urls() {
+    let file_path = "tests/mocks/guilds-jaguna-200.html";
+    let mut file = std::fs::File::open(file_path).unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).unwrap();
+    // older pages link the logos relative to tibia.com
+    let body = buf.iter().map(|c| *c as char).collect::<String>().replace(
+        "https://static.tibia.com/images/guildlogos/Black_Crown.gif",
+        "/community/img/guilds/Black_Crown.gif",
+    );
+
+    let client = MockedClient::new().body(&body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Jaguna/guilds"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let guilds = received_json.as_array().unwrap();
+    let black_crown = guilds.iter().find(|g| g["name"] == "Black Crown").unwrap();
+    assert_eq!(
+        "https://www.tibia.com/community/img/guilds/Black_Crown.gif",
+        black_crown["logo"]
+    );
+    assert!(guilds
+        .iter()
+        .all(|g| g["logo"].as_str().unwrap().starts_with("https://")));
+}
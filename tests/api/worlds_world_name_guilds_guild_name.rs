@@ -0,0 +1,105 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+/// The guilds of Jaguna, with Black Crown renamed to Red Rose
+fn guilds_body() -> String {
+    let buf = std::fs::read("tests/mocks/guilds-jaguna-200.html").unwrap();
+    // converts iso-8859-1 to utf-8 so we can represent it as a string
+    buf.iter()
+        .map(|c| *c as char)
+        .collect::<String>()
+        .replace("Black Crown", "Red Rose")
+}
+
+#[tokio::test]
+async fn can_get_a_guild_of_a_world() {
+    let guilds_body = guilds_body();
+    let client = MockedClient::new()
+        .page_body("fetch_guilds_page", &guilds_body)
+        .page_body(
+            "fetch_guild_page",
+            include_str!("../mocks/guild-red_rose-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds/red%20rose?perPage=2"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(
+        "/api/v1/worlds/Jaguna/guilds/red%20rose",
+        received_json["guildUrl"]
+    );
+    assert_eq!("Urinchoklad", received_json["founder"]);
+    assert_eq!(5, received_json["memberCount"]);
+    assert_eq!(
+        2,
+        received_json["members"]["data"].as_array().unwrap().len()
+    );
+}
+
+#[tokio::test]
+async fn returns_404_for_guild_of_another_world() {
+    let guilds_body = guilds_body();
+    let client = MockedClient::new()
+        .page_body("fetch_guilds_page", &guilds_body)
+        .page_body(
+            "fetch_guild_page",
+            include_str!("../mocks/guild-blood_oath-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds/Blood%20Oath"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_guild() {
+    let guilds_body = guilds_body();
+    let client = MockedClient::new()
+        .page_body("fetch_guilds_page", &guilds_body)
+        .page_body(
+            "fetch_guild_page",
+            include_str!("../mocks/guild-invalid_guild-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds/invalid_guild"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/guilds/Red%20Rose"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+}
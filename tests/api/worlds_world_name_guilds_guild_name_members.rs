@@ -0,0 +1,143 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+/// The guilds of Jaguna, with Black Crown renamed to Red Rose
+fn guilds_body() -> String {
+    let buf = std::fs::read("tests/mocks/guilds-jaguna-200.html").unwrap();
+    // converts iso-8859-1 to utf-8 so we can represent it as a string
+    buf.iter()
+        .map(|c| *c as char)
+        .collect::<String>()
+        .replace("Black Crown", "Red Rose")
+}
+
+/// Gets the names of the Red Rose members matching `query`
+async fn member_names(query: &str) -> Vec<String> {
+    let guilds_body = guilds_body();
+    let client = MockedClient::new()
+        .page_body("fetch_guilds_page", &guilds_body)
+        .page_body(
+            "fetch_guild_page",
+            include_str!("../mocks/guild-red_rose-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds/Red%20Rose/members?{query}"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    received_json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn can_get_guild_members() {
+    assert_eq!(
+        vec![
+            "Urinchoklad",
+            "Mad Bubble",
+            "Elder Bob",
+            "Sir Helper",
+            "Bubble"
+        ],
+        member_names("").await
+    );
+}
+
+#[tokio::test]
+async fn can_filter_by_vocation() {
+    assert_eq!(
+        vec!["Urinchoklad", "Bubble"],
+        member_names("vocation=knight").await
+    );
+}
+
+#[tokio::test]
+async fn can_filter_by_rank() {
+    assert_eq!(
+        vec!["Mad Bubble", "Elder Bob"],
+        member_names("rank=Vice%20Leader").await
+    );
+}
+
+#[tokio::test]
+async fn can_filter_by_status() {
+    assert_eq!(
+        vec!["Urinchoklad", "Elder Bob"],
+        member_names("status=online").await
+    );
+    assert_eq!(
+        vec!["Mad Bubble", "Sir Helper", "Bubble"],
+        member_names("status=offline").await
+    );
+}
+
+#[tokio::test]
+async fn can_filter_by_name_ignoring_case() {
+    assert_eq!(
+        vec!["Mad Bubble", "Bubble"],
+        member_names("nameContains=BUBBLE").await
+    );
+}
+
+#[tokio::test]
+async fn combines_filters() {
+    assert_eq!(
+        vec!["Bubble"],
+        member_names("nameContains=bubble&status=offline&vocation=knight").await
+    );
+}
+
+#[tokio::test]
+async fn returns_404_for_guild_of_another_world() {
+    let guilds_body = guilds_body();
+    let client = MockedClient::new()
+        .page_body("fetch_guilds_page", &guilds_body)
+        .page_body(
+            "fetch_guild_page",
+            include_str!("../mocks/guild-blood_oath-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds/Blood%20Oath/members"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_guild() {
+    let guilds_body = guilds_body();
+    let client = MockedClient::new()
+        .page_body("fetch_guilds_page", &guilds_body)
+        .page_body(
+            "fetch_guild_page",
+            include_str!("../mocks/guild-invalid_guild-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/guilds/invalid_guild/members"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
@@ -0,0 +1,115 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn can_get_highscores() {
+    let body = include_str!("../mocks/highscores-antica-experience-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/highscores/experience"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(1, received_json["page"]);
+    assert_eq!(25, received_json["perPage"]);
+    assert_eq!(300, received_json["totalItems"]);
+    assert_eq!(12, received_json["totalPages"]);
+
+    let entries = received_json["data"].as_array().unwrap();
+    assert_eq!(5, entries.len());
+    assert_eq!(
+        json!({
+            "rank": 1,
+            "name": "Sir Helper",
+            "vocation": "royalPaladin",
+            "level": 512,
+            "value": 2212345678u64
+        }),
+        entries[0]
+    );
+    assert_eq!(
+        json!({ "rank": 5, "name": "Rookstayer", "level": 8, "value": 4200 }),
+        entries[4]
+    );
+}
+
+#[tokio::test]
+async fn can_get_other_pages() {
+    let body = include_str!("../mocks/highscores-antica-experience-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/highscores/magicLevel?page=12&vocation=druid"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(12, received_json["page"]);
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_page() {
+    let body = include_str!("../mocks/highscores-antica-experience-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    for page in [0, 13] {
+        let response = reqwest::get(format!(
+            "http://{addr}/api/v1/worlds/Antica/highscores/experience?page={page}"
+        ))
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_category() {
+    let body = include_str!("../mocks/highscores-antica-experience-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/highscores/charm"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_world() {
+    let body = include_str!("../mocks/highscores-invalid_world-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/invalid_world/highscores/experience"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
@@ -25,6 +25,40 @@ async fn can_get_guilds() {
     assert_eq!(expected_json, received_json);
 }
 
+#[tokio::test]
+async fn sums_the_deaths_of_every_race() {
+    let body = include_str!("../mocks/killstatistics-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/kill-statistics"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let races = received_json["races"].as_array().unwrap();
+    let killed_players = |period: &str| {
+        races
+            .iter()
+            .map(|race| race[period]["killedPlayers"].as_u64().unwrap())
+            .sum::<u64>()
+    };
+
+    assert_eq!(
+        killed_players("lastDay"),
+        received_json["totalDeathsLastDay"]
+    );
+    assert_eq!(
+        killed_players("lastWeek"),
+        received_json["totalDeathsLastWeek"]
+    );
+}
+
 #[tokio::test]
 async fn returns_404_for_invalid_world() {
     let body = include_str!("../mocks/killstatistics-invalid_world-200.html");
@@ -57,3 +91,82 @@ async fn sends_503_when_maintenance() {
 
     assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status())
 }
+
+/// Gets the races of Antica sorted by `sort`
+async fn sorted_races(sort: &str) -> Vec<Value> {
+    let body = include_str!("../mocks/killstatistics-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/kill-statistics?sort={sort}"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    received_json["races"].as_array().unwrap().clone()
+}
+
+/// Asserts that `key` of each race is never greater than the one before it
+fn assert_descending(races: &[Value], key: impl Fn(&Value) -> u64) {
+    assert!(races.windows(2).all(|w| key(&w[0]) >= key(&w[1])));
+}
+
+#[tokio::test]
+async fn can_sort_races_by_name() {
+    let ascending = sorted_races("raceAsc").await;
+    let mut descending = sorted_races("raceDesc").await;
+
+    assert!(ascending
+        .windows(2)
+        .all(|w| w[0]["race"].as_str() <= w[1]["race"].as_str()));
+    descending.reverse();
+    assert_eq!(ascending, descending);
+}
+
+#[tokio::test]
+async fn can_sort_races_by_killed_players() {
+    let races = sorted_races("killedPlayersDesc").await;
+
+    assert_descending(&races, |r| r["lastDay"]["killedPlayers"].as_u64().unwrap());
+    assert_ne!(0, races[0]["lastDay"]["killedPlayers"]);
+}
+
+#[tokio::test]
+async fn can_sort_races_by_killed_by_players() {
+    let races = sorted_races("killedByPlayersDesc").await;
+
+    assert_descending(&races, |r| {
+        r["lastDay"]["killedByPlayers"].as_u64().unwrap()
+    });
+}
+
+#[tokio::test]
+async fn can_sort_races_by_total_kills() {
+    let races = sorted_races("totalKillsDesc").await;
+
+    assert_descending(&races, |r| {
+        r["lastDay"]["killedPlayers"].as_u64().unwrap()
+            + r["lastDay"]["killedByPlayers"].as_u64().unwrap()
+    });
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_sort() {
+    let body = include_str!("../mocks/killstatistics-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/kill-statistics?sort=killedPlayers"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
@@ -0,0 +1,54 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn can_get_race_kill_statistics() {
+    let body = include_str!("../mocks/killstatistics-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/kill-statistics/Dragons"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(
+        json!({
+            "race": "dragons",
+            "lastDay": { "killedPlayers": 3, "killedByPlayers": 8285 },
+            "lastWeek": { "killedPlayers": 34, "killedByPlayers": 61817 },
+            "dailyAverageKilledPlayers": 4.86,
+            "dailyAverageKilledByPlayers": 8831.0,
+            "totalLastDay": { "killedPlayers": 850, "killedByPlayers": 1110296 },
+            "totalLastWeek": { "killedPlayers": 4830, "killedByPlayers": 8236238 }
+        }),
+        received_json
+    );
+}
+
+#[tokio::test]
+async fn returns_404_for_unknown_race() {
+    let body = include_str!("../mocks/killstatistics-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    // the race has to match exactly
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/kill-statistics/Dragon"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("RESOURCE_NOT_FOUND", received_json["code"]);
+}
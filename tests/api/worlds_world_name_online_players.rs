@@ -0,0 +1,168 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+async fn can_get_online_players() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/online-players?page=2&perPage=100"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let expected = include_str!("../mocks/world-antica-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+    let players_online = expected_json["playersOnline"].as_array().unwrap();
+
+    assert_eq!(2, received_json["page"]);
+    assert_eq!(100, received_json["perPage"]);
+    assert_eq!(players_online.len(), received_json["totalItems"]);
+    assert_eq!(5, received_json["totalPages"]);
+    assert_eq!(
+        Value::from(players_online[100..200].to_vec()),
+        received_json["data"]
+    );
+}
+
+#[tokio::test]
+async fn can_filter_online_players() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/online-players?vocation=eliteKnight&levelMin=100&levelMax=200"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let players = received_json["data"].as_array().unwrap();
+
+    assert_eq!(22, received_json["totalItems"]);
+    assert_eq!(22, players.len());
+    assert!(players.iter().all(|p| {
+        let level = p["level"].as_u64().unwrap();
+        p["vocation"] == "eliteKnight" && (100..=200).contains(&level)
+    }));
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_vocation() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/online-players?vocation=invalid"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let keys = received_json
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["code", "message"], keys);
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+    assert!(received_json["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid value for parameter 'vocation': expected one of"));
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_number() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/online-players?levelMin=abc"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+    assert!(received_json["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid value for parameter 'levelMin'"));
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_page() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/online-players?page=0"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+}
+
+#[tokio::test]
+async fn returns_no_players_far_past_the_last_page() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/online-players?page=4294967295&perPage=100"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(4294967295u32, received_json["page"]);
+    assert_eq!(5, received_json["totalPages"]);
+    assert_eq!(Value::from(Vec::<Value>::new()), received_json["data"]);
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_world() {
+    let body = include_str!("../mocks/world-invalid_world-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/invalid_world/online-players"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
@@ -1,7 +1,7 @@
 use super::*;
 use pretty_assertions::assert_eq;
 use reqwest::StatusCode;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 #[tokio::test]
 async fn can_get_residences() {
@@ -57,6 +57,81 @@ async fn can_get_residences() {
     assert_eq!(expected_json, received_json);
 }
 
+#[tokio::test]
+async fn accepts_any_casing_of_town() {
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/residences?town=eDRON&type=house"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let residences = received_json.as_array().unwrap();
+    assert!(!residences.is_empty());
+    assert!(residences.iter().all(|r| r["town"] == "Edron"));
+}
+
+#[tokio::test]
+async fn includes_the_world_of_each_residence() {
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/jaguna/residences?town=Edron&type=house"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let residences = received_json.as_array().unwrap();
+    assert!(!residences.is_empty());
+    assert!(residences.iter().all(|r| r["world"] == "Jaguna"));
+}
+
+#[tokio::test]
+async fn can_get_auctions_ending_within_the_hour() {
+    let body = include_str!("../mocks/houses-jaguna-edron-ending-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/residences?town=Edron&type=house"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let residences = received_json.as_array().unwrap();
+    let status_of = |name: &str| {
+        residences
+            .iter()
+            .find(|r| r["name"] == name)
+            .map(|r| r["status"].clone())
+            .unwrap()
+    };
+
+    for (name, bid) in [("Castle Street 1", 2000000), ("Castle Street 2", 2500000)] {
+        let status = status_of(name);
+        assert_eq!("auctionWithBid", status["type"]);
+        assert_eq!(bid, status["bid"]);
+        assert!(status["expiryTime"].is_string());
+    }
+}
+
 #[tokio::test]
 async fn returns_404_for_invalid_world() {
     let body = include_str!("../mocks/houses-invalid_world-edron-200.html");
@@ -88,3 +163,152 @@ async fn returns_404_for_invalid_town() {
     .unwrap();
     assert_eq!(StatusCode::NOT_FOUND, response.status());
 }
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Antica/residences?town=Thais"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(
+        json!({ "message": "The tibia website failed to process the underlying request" }),
+        received_json
+    );
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance_no_town_filter() {
+    let client = MockedClient::new()
+        .page_body("fetch_towns_page", include_str!("../mocks/towns-200.html"))
+        .page_body(
+            "fetch_residences_page",
+            include_str!("../mocks/maintenance-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    // the residences of every town are fetched in parallel
+    reqwest::get(format!("http://{addr}/api/v1/towns"))
+        .await
+        .unwrap();
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Antica/residences"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!(
+        json!({ "message": "The tibia website failed to process the underlying request" }),
+        received_json
+    );
+}
+
+#[tokio::test]
+async fn returns_500_for_unexpected_page_structure() {
+    // the world and town exist, but the houses table is gone
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html").replacen(
+        r#"class="TableContent""#,
+        r#"class="Removed""#,
+        1,
+    );
+    let client = MockedClient::new().body(&body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/residences?town=Edron&type=house"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+}
+
+#[tokio::test]
+async fn can_get_residences_as_csv() {
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/residences?town=Edron&type=house&format=csv"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("text/csv", response.headers()["content-type"]);
+    assert_eq!(
+        "attachment; filename=\"residences.csv\"",
+        response.headers()["content-disposition"]
+    );
+    assert!(response.headers()["x-tibia-data-age"]
+        .to_str()
+        .unwrap()
+        .parse::<u64>()
+        .is_ok());
+
+    let text = response.text().await.unwrap();
+    let mut lines = text.lines();
+
+    assert_eq!(
+        Some("id,world,town,type,name,size,rent,status,bid,expiryTime"),
+        lines.next()
+    );
+    assert_eq!(
+        Some("50101,Jaguna,Edron,house,Castle Shop 1,42,400000,rented,,"),
+        lines.next()
+    );
+}
+
+#[tokio::test]
+async fn accepts_plural_residence_types() {
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/residences?town=Edron&type=houses"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn returns_400_for_invalid_residence_type() {
+    let body = include_str!("../mocks/houses-jaguna-edron-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!(
+        "http://{addr}/api/v1/worlds/Jaguna/residences?town=Edron&type=castle"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("INVALID_PARAMETER", received_json["code"]);
+    assert!(received_json["message"]
+        .as_str()
+        .unwrap()
+        .contains("expected one of: house, guildhall"));
+}
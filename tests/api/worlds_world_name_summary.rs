@@ -0,0 +1,126 @@
+use std::io::Read;
+
+use super::*;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn can_get_world_summary() {
+    let file_path = "tests/mocks/guilds-jaguna-200.html";
+    let mut file = std::fs::File::open(file_path).unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).unwrap();
+    // converts iso-8859-1 to utf-8 so we can represent it as a string
+    let guilds_body = buf.iter().map(|c| *c as char).collect::<String>();
+
+    let client = MockedClient::new()
+        .page_body(
+            "fetch_towns_page",
+            include_str!("../mocks/towns-edron-200.html"),
+        )
+        .page_body(
+            "fetch_world_details_page",
+            include_str!("../mocks/world-antica-200.html"),
+        )
+        .page_body("fetch_guilds_page", &guilds_body)
+        .page_body(
+            "fetch_residences_page",
+            include_str!("../mocks/houses-jaguna-edron-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    // the towns are needed to scan the house auctions
+    reqwest::get(format!("http://{addr}/api/v1/towns"))
+        .await
+        .unwrap();
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Jaguna/summary"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    let expected_guilds = include_str!("../mocks/guilds-jaguna-200.json");
+    let expected_guilds = serde_json::from_str::<Value>(expected_guilds).unwrap();
+
+    assert_eq!("Jaguna", received_json["name"]);
+    assert_eq!(
+        expected_guilds.as_array().unwrap().len(),
+        received_json["guildCount"]
+    );
+    assert_eq!(
+        json!({
+            "druid": 5,
+            "elderDruid": 101,
+            "eliteKnight": 120,
+            "knight": 19,
+            "masterSorcerer": 74,
+            "none": 7,
+            "paladin": 11,
+            "royalPaladin": 109,
+            "sorcerer": 9
+        }),
+        received_json["playersOnlineByVocation"]
+    );
+    assert_eq!(104, received_json["activeHouseAuctions"]);
+}
+
+#[tokio::test]
+async fn returns_nulls_for_partial_failures() {
+    let client = MockedClient::new()
+        .page_body(
+            "fetch_world_details_page",
+            include_str!("../mocks/world-antica-200.html"),
+        )
+        .page_body(
+            "fetch_guilds_page",
+            include_str!("../mocks/maintenance-200.html"),
+        );
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Antica/summary"))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let received_json = response.json::<Value>().await.unwrap();
+    assert_eq!("Antica", received_json["name"]);
+    assert_eq!(Value::Null, received_json["guildCount"]);
+    assert_eq!(Value::Null, received_json["activeHouseAuctions"]);
+    assert_eq!(455, received_json["playersOnlineCount"]);
+}
+
+#[tokio::test]
+async fn returns_404_for_invalid_world() {
+    let body = include_str!("../mocks/world-invalid_world-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/invalid_world/summary"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn sends_503_when_maintenance() {
+    let body = include_str!("../mocks/maintenance-200.html");
+    let client = MockedClient::default().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let response = reqwest::get(format!("http://{addr}/api/v1/worlds/Antica/summary"))
+        .await
+        .unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+}
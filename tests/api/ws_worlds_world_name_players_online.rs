@@ -0,0 +1,37 @@
+use super::*;
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+#[tokio::test]
+async fn streams_players_online_count() {
+    let body = include_str!("../mocks/world-antica-200.html");
+    let client = MockedClient::new().body(body);
+
+    let state = AppState::with_client(client);
+    let addr = spawn_app(state);
+
+    let (mut socket, _) = connect_async(format!("ws://{addr}/ws/v1/worlds/Antica/players-online"))
+        .await
+        .unwrap();
+
+    let message = tokio::time::timeout(std::time::Duration::from_secs(5), socket.next())
+        .await
+        .expect("To receive a message")
+        .unwrap()
+        .unwrap();
+    let Message::Text(text) = message else {
+        panic!("Expected a text message, got {:?}", message);
+    };
+
+    let received_json = serde_json::from_str::<Value>(&text).unwrap();
+    let expected = include_str!("../mocks/world-antica-200.json");
+    let expected_json = serde_json::from_str::<Value>(expected).unwrap();
+
+    assert_eq!(
+        expected_json["playersOnlineCount"],
+        received_json["playersOnlineCount"]
+    );
+    assert!(received_json["timestamp"].is_string());
+}